@@ -1,7 +1,6 @@
 use untrusted_value::derive::untrusted_inputs;
 use untrusted_value::derive::UntrustedVariant;
 use untrusted_value::SanitizeValue;
-use untrusted_value_derive_internals::IntoUntrustedVariant;
 
 // note:
 // - trusted version: support debugs
@@ -0,0 +1,56 @@
+use untrusted_value::derive::untrusted_inputs;
+use untrusted_value::SanitizeWith;
+
+#[derive(Debug, PartialEq)]
+enum RequestError {
+    User,
+    Path,
+    Body,
+}
+
+fn sanitize_user(user: &str) -> Result<&str, RequestError> {
+    if !user.is_empty() && user.chars().all(|c| c.is_ascii_alphanumeric()) {
+        Ok(user)
+    } else {
+        Err(RequestError::User)
+    }
+}
+
+fn sanitize_path(path: &str) -> Result<&str, RequestError> {
+    if path.split('/').any(|segment| segment == "..") {
+        Err(RequestError::Path)
+    } else {
+        Ok(path)
+    }
+}
+
+fn sanitize_body(body: &[u8]) -> Result<&str, RequestError> {
+    std::str::from_utf8(body).map_err(|_| RequestError::Body)
+}
+
+// Imagine: some webserver specification
+// #[oai(path = "/:user/*path"), method = "post"]
+// All reference arguments are wrapped as `UntrustedValue<&T>`, keeping their lifetimes.
+#[untrusted_inputs]
+fn upload<'a>(user: &str, path: &'a str, body: &[u8]) -> Result<(String, &'a str), RequestError> {
+    let user = user.sanitize_with(sanitize_user)?;
+    let path = path.sanitize_with(sanitize_path)?;
+    let body = body.sanitize_with(sanitize_body)?;
+
+    Ok((format!("{user} uploaded {} bytes", body.len()), path))
+}
+
+fn main() {
+    assert_eq!(
+        upload("alice", "notes/todo.txt", b"buy milk"),
+        Ok(("alice uploaded 8 bytes".to_string(), "notes/todo.txt"))
+    );
+    assert_eq!(
+        upload("alice", "../../etc/passwd", b""),
+        Err(RequestError::Path)
+    );
+    assert_eq!(
+        upload("alice", "notes/todo.txt", &[0xff, 0xfe]),
+        Err(RequestError::Body)
+    );
+}
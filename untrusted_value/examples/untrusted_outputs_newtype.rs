@@ -0,0 +1,49 @@
+use untrusted_value::{SanitizeValue, SanitizeWith, UntrustedValue};
+
+// Some library providing untrusted data
+mod library {
+    use untrusted_value::derive::untrusted_output;
+    use untrusted_value::{SanitizeValue, SanitizeWith};
+
+    // `SanitizeValue<String>` can not be implemented for `UntrustedValue<String>` outside
+    // of the untrusted_value crate, since both types are foreign. The newtype is local,
+    // so the library can provide the sanitization for its output.
+    #[untrusted_output(newtype = UntrustedUsername)]
+    pub fn current_username() -> String {
+        "alice".to_string()
+    }
+
+    impl SanitizeValue<String> for UntrustedUsername {
+        type Error = ();
+
+        fn sanitize_value(self) -> Result<String, Self::Error> {
+            self.0.sanitize_with(|name: String| {
+                if name.chars().all(|c| c.is_ascii_alphanumeric()) {
+                    Ok(name)
+                } else {
+                    Err(())
+                }
+            })
+        }
+    }
+}
+
+fn main() {
+    // call library function, using the library's sanitization
+    let username: String = library::current_username()
+        .sanitize_value()
+        .expect("Sanitization failed");
+    assert_eq!(username, "alice");
+
+    // use an own sanitizer by converting the newtype into an `UntrustedValue`
+    let username: UntrustedValue<String> = library::current_username().into();
+    let initial = username
+        .sanitize_with(|name| name.chars().next().ok_or(()))
+        .expect("Sanitization failed");
+    assert_eq!(initial, 'a');
+
+    // reuse the library's sanitization for a username obtained from another source
+    let username = UntrustedValue::from("<script>".to_string());
+    let username = library::UntrustedUsername::from(username).sanitize_value();
+    assert_eq!(username, Err(()));
+}
@@ -0,0 +1,40 @@
+use untrusted_value::derive::UntrustedVariant;
+use untrusted_value::{IntoUntrustedVariant, SanitizeValue, UntrustedValue};
+
+// The untrusted variant of a generic struct is generic as well
+#[derive(UntrustedVariant)]
+#[untrusted_derive(Clone, SanitizeValueEnd)]
+pub struct Setting<T>
+where
+    T: Clone,
+{
+    pub name: String,
+    pub value: T,
+}
+
+/// Sanitize the tainted version of `Setting<T>`
+impl<T: Clone> SanitizeValue<Setting<T>> for SettingUntrusted<T> {
+    type Error = ();
+
+    fn sanitize_value(self) -> Result<Setting<T>, Self::Error> {
+        Ok(Setting {
+            name: self.name.use_untrusted_value(),
+            value: self.value.use_untrusted_value(),
+        }) // in real application: do some sanitizing
+    }
+}
+
+fn main() {
+    let setting = UntrustedValue::from(Setting {
+        name: "timeout".to_string(),
+        value: 30u32,
+    });
+
+    // UntrustedValue<Setting<u32>> -> sanitize_value -> Setting<u32>
+    let setting: Setting<u32> = setting.sanitize_value().expect("Sanitization failed");
+    assert_eq!(setting.value, 30);
+
+    // Setting<u32> -> to_untrusted_variant -> SettingUntrusted<u32> -> sanitize_value -> Setting<u32>
+    let setting: SettingUntrusted<u32> = setting.to_untrusted_variant();
+    let _setting: Setting<u32> = setting.sanitize_value().expect("Sanitization failed");
+}
@@ -0,0 +1,97 @@
+use super::UntrustedValue;
+
+/// Converts a value into an [`UntrustedValue`], without wrapping already tainted values twice.
+///
+/// In contrast to the blanket `From<Insecure>` implementation of [`UntrustedValue`], this trait
+/// is not implemented for all types. It is implemented as a no-op for `UntrustedValue<Insecure>`
+/// and by wrapping for common plain types. Implement it for your own types by wrapping the value:
+///
+/// ```rust
+/// use untrusted_value::{IntoUntrustedValue, UntrustedValue};
+///
+/// pub struct Username(String);
+///
+/// impl IntoUntrustedValue for Username {
+///     type Insecure = Username;
+///
+///     fn into_untrusted_value(self) -> UntrustedValue<Username> {
+///         UntrustedValue::wrap(self)
+///     }
+/// }
+///
+/// let name = Username("alice".to_string()).into_untrusted_value();
+/// let name: UntrustedValue<Username> = name.into_untrusted_value();
+/// # assert_eq!(name.use_untrusted_value().0, "alice");
+/// ```
+///
+/// Due to the orphan rules, downstream crates can not implement this trait for `UntrustedValue<_>`,
+/// so converting a tainted value always yields the same tainted value:
+/// ```compile_fail
+/// use untrusted_value::{IntoUntrustedValue, UntrustedValue};
+///
+/// pub struct Username(String);
+///
+/// impl IntoUntrustedValue for UntrustedValue<Username> { // error: only traits defined in the current crate
+///     type Insecure = UntrustedValue<Username>;
+///
+///     fn into_untrusted_value(self) -> UntrustedValue<UntrustedValue<Username>> {
+///         UntrustedValue::wrap(self)
+///     }
+/// }
+/// ```
+pub trait IntoUntrustedValue {
+    /// The type of the tainted value.
+    type Insecure;
+
+    /// Wraps the value as [`UntrustedValue`], unless it already is tainted.
+    fn into_untrusted_value(self) -> UntrustedValue<Self::Insecure>;
+}
+
+impl<Insecure> IntoUntrustedValue for UntrustedValue<Insecure> {
+    type Insecure = Insecure;
+
+    fn into_untrusted_value(self) -> UntrustedValue<Insecure> {
+        self
+    }
+}
+
+macro_rules! impl_into_untrusted_value {
+    ($($insecure:ty $(, $generic:ident)*;)*) => {
+        $(
+            impl<$($generic),*> IntoUntrustedValue for $insecure {
+                type Insecure = $insecure;
+
+                fn into_untrusted_value(self) -> UntrustedValue<$insecure> {
+                    UntrustedValue::wrap(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_into_untrusted_value! {
+    bool; char;
+    u8; u16; u32; u64; u128; usize;
+    i8; i16; i32; i64; i128; isize;
+    f32; f64;
+    String;
+    Vec<T>, T;
+    Box<T>, T;
+    Option<T>, T;
+}
+
+impl<'a> IntoUntrustedValue for &'a str {
+    type Insecure = &'a str;
+
+    fn into_untrusted_value(self) -> UntrustedValue<&'a str> {
+        UntrustedValue::wrap(self)
+    }
+}
+
+impl<'a, T> IntoUntrustedValue for &'a [T] {
+    type Insecure = &'a [T];
+
+    fn into_untrusted_value(self) -> UntrustedValue<&'a [T]> {
+        UntrustedValue::wrap(self)
+    }
+}
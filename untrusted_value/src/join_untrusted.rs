@@ -0,0 +1,41 @@
+use super::UntrustedValue;
+
+/// Extension trait to join tainted strings into a single tainted string.
+///
+/// This trait is implemented for all iterables yielding [`UntrustedValue`]s of strings.
+pub trait JoinUntrusted {
+    /// Joins all parts, separated by the given separator. The result stays tainted.
+    ///
+    /// ```rust
+    /// use untrusted_value::{JoinUntrusted, UntrustedValue};
+    ///
+    /// let parts = vec![
+    ///     UntrustedValue::from("home".to_string()),
+    ///     UntrustedValue::from("alice".to_string()),
+    ///     UntrustedValue::from("notes.txt".to_string()),
+    /// ];
+    ///
+    /// let path: UntrustedValue<String> = parts.join_untrusted("/");
+    /// assert_eq!(path.use_untrusted_value(), "home/alice/notes.txt");
+    /// ```
+    fn join_untrusted(self, separator: &str) -> UntrustedValue<String>;
+}
+
+impl<Insecure, Iter> JoinUntrusted for Iter
+where
+    Insecure: AsRef<str>,
+    Iter: IntoIterator<Item = UntrustedValue<Insecure>>,
+{
+    fn join_untrusted(self, separator: &str) -> UntrustedValue<String> {
+        let mut joined = String::new();
+
+        for (index, part) in self.into_iter().enumerate() {
+            if index > 0 {
+                joined.push_str(separator);
+            }
+            joined.push_str(part.use_untrusted_value().as_ref());
+        }
+
+        UntrustedValue::wrap(joined)
+    }
+}
@@ -0,0 +1,48 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// A sanitization error together with a label describing which untrusted value failed
+/// to sanitize. See [`crate::UntrustedValue::sanitize_with_labeled`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LabeledError<E> {
+    /// Label of the untrusted value that failed to sanitize
+    pub label: &'static str,
+    /// The error returned by the sanitizer
+    pub error: E,
+}
+
+impl<E> LabeledError<E> {
+    /// Returns the wrapped error, dropping the label
+    pub fn into_inner(self) -> E {
+        self.error
+    }
+}
+
+/// Only the label is printed, the wrapped error is available as [`Error::source`].
+/// This way error reporters that print the chain of sources do not print the wrapped error twice.
+///
+/// ```rust
+/// use std::error::Error;
+/// use untrusted_value::UntrustedValue;
+///
+/// let error = UntrustedValue::from("http")
+///     .sanitize_with_labeled("port", |value| value.parse::<u16>())
+///     .expect_err("Sanitization succeeded");
+///
+/// assert_eq!(error.to_string(), "failed to sanitize `port`");
+/// assert_eq!(
+///     error.source().map(ToString::to_string),
+///     Some(error.error.to_string())
+/// );
+/// ```
+impl<E> Display for LabeledError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "failed to sanitize `{}`", self.label)
+    }
+}
+
+impl<E: Error + 'static> Error for LabeledError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
@@ -139,9 +139,22 @@
 //!
 //! Optional features:
 //!  * `derive_harden_sanitize`: enables hardening for the derive macro `SanitizeValue`. When this feature is disabled, the
-//!     implemented `fn sanitize_value(self)` errors-early. Which may be undesired if sanitizing timing side
-//!     channels are a concern. When enabling this feature, first all sanitizers are run, then
-//!     the first error is propagated.
+//!    implemented `fn sanitize_value(self)` errors-early. Which may be undesired if sanitizing timing side
+//!    channels are a concern. When enabling this feature, first all sanitizers are run, then
+//!    the first error is propagated.
+//!  * `bytes`: provides helpers to split and slice tainted `bytes::Bytes` buffers while keeping the taint.
+//!  * `bytemuck`: provides helpers to reinterpret tainted plain old data, like `UntrustedValue::cast`, while keeping the taint.
+//!  * `validator`: provides `UntrustedValue::sanitize_validated` to sanitize values using the `validator` crate.
+//!  * `inspect_untrusted`: enables methods that inspect the tainted value without removing the taint, like
+//!    `UntrustedValue::check`. These methods expose the tainted value to the caller, so their usage should be reviewed.
+//!  * `std` (enabled by default): provides common sanitizers for tainted strings, like
+//!    `UntrustedValue::sanitize_trimmed_nonempty` and `UntrustedValue::sanitize_ascii_alphanumeric`,
+//!    and `UntrustedValue::sanitize_within` to prevent path traversal.
+//!  * `async`: provides `UntrustedValue::sanitize_with_async` to sanitize values using asynchronous sanitizers.
+//!  * `constant_time`: provides `UntrustedValue::sanitize_eq` to compare tainted bytes against a secret
+//!    in constant time, using the `subtle` crate.
+//!  * `catch_panic`: provides `UntrustedValue::sanitize_with_catch`, which turns a panicking sanitizer into
+//!    a sanitization error instead of crashing.
 //!
 //! ## Runtime overhead
 //! When using compile optimizations there should be no runtime overhead since
@@ -182,3 +195,23 @@ pub use untrusted_value::*;
 
 mod maybe_untrusted;
 pub use maybe_untrusted::*;
+
+mod sanitize_iterator;
+pub use sanitize_iterator::*;
+
+mod sanitize_option;
+pub use sanitize_option::*;
+
+mod join_untrusted;
+pub use join_untrusted::*;
+
+mod into_untrusted_value;
+pub use into_untrusted_value::*;
+
+mod labeled_error;
+pub use labeled_error::*;
+
+#[cfg(feature = "std")]
+mod sanitization_error;
+#[cfg(feature = "std")]
+pub use sanitization_error::*;
@@ -1,5 +1,6 @@
 use super::UntrustedValue;
-use untrusted_value_derive_internals::{SanitizeValue, SanitizeWith};
+use std::fmt::{Debug, Formatter};
+use untrusted_value_derive_internals::{DefaultSanitizer, SanitizeValue, SanitizeWith};
 
 /// Represents a value that might be untrusted. See `UntrustedValue` for more information.
 pub enum MaybeUntrusted<Insecure, Trusted = Insecure> {
@@ -45,6 +46,36 @@ impl<Insecure, Trusted> MaybeUntrusted<Insecure, Trusted> {
         !self.is_untrusted()
     }
 
+    /// Borrows the contained value, keeping whether it is trusted or untrusted.
+    ///
+    /// ```rust
+    /// use untrusted_value::{MaybeUntrusted, SanitizeWith};
+    ///
+    /// let trusted = MaybeUntrusted::<String>::wrap_ok("alice".to_string());
+    /// let untrusted = MaybeUntrusted::<String>::wrap_untrusted("bob".to_string());
+    ///
+    /// assert!(trusted.as_ref().is_ok());
+    /// assert!(untrusted.as_ref().is_untrusted());
+    ///
+    /// let name = untrusted
+    ///     .as_ref()
+    ///     .sanitize_with(|name| Ok::<&String, ()>(name))
+    ///     .expect("Sanitization failed");
+    /// assert_eq!(name, "bob");
+    ///
+    /// let name = trusted
+    ///     .as_ref()
+    ///     .sanitize_with(|_| Err(()))
+    ///     .expect("Trusted value is not sanitized");
+    /// assert_eq!(name, "alice");
+    /// ```
+    pub fn as_ref(&self) -> MaybeUntrusted<&Insecure, &Trusted> {
+        match self {
+            MaybeUntrusted::Ok(value) => MaybeUntrusted::Ok(value),
+            MaybeUntrusted::Untrusted(value) => MaybeUntrusted::Untrusted(value.as_ref()),
+        }
+    }
+
     /// Wraps the provided values as Untrusted
     pub fn wrap_untrusted(value: Insecure) -> Self {
         MaybeUntrusted::Untrusted(value.into())
@@ -54,6 +85,200 @@ impl<Insecure, Trusted> MaybeUntrusted<Insecure, Trusted> {
     pub fn wrap_ok(value: Trusted) -> Self {
         MaybeUntrusted::Ok(value)
     }
+
+    /// Wraps the provided value as Ok. Same as [`MaybeUntrusted::wrap_ok`].
+    ///
+    /// There is intentionally no `From<Trusted>` implementation: since `Insecure` and `Trusted`
+    /// may be the same type, `MaybeUntrusted::from(value)` would not make clear whether the value
+    /// is wrapped as trusted or untrusted. Converting an [`UntrustedValue`] using `From` always
+    /// yields the Untrusted variant.
+    ///
+    /// ```rust
+    /// use untrusted_value::{MaybeUntrusted, UntrustedValue};
+    ///
+    /// let trusted = MaybeUntrusted::<&str>::trusted("localhost");
+    /// assert!(trusted.is_ok());
+    ///
+    /// let untrusted = MaybeUntrusted::<&str>::from(UntrustedValue::from("localhost"));
+    /// assert!(untrusted.is_untrusted());
+    /// ```
+    pub fn trusted(value: Trusted) -> Self {
+        Self::wrap_ok(value)
+    }
+
+    /// Wraps the trusted value as Ok if present, otherwise obtains a value
+    /// from `untrusted` and wraps it as Untrusted.
+    ///
+    /// `untrusted` is only called if no trusted value is provided.
+    ///
+    /// ```rust
+    /// use untrusted_value::MaybeUntrusted;
+    ///
+    /// let cache_hit = MaybeUntrusted::<String>::trusted_or_untrusted(Some("cached".into()), || {
+    ///     unreachable!("not fetched on a cache hit")
+    /// });
+    /// assert!(cache_hit.is_ok());
+    ///
+    /// let cache_miss = MaybeUntrusted::<String>::trusted_or_untrusted(None, || "fetched".into());
+    /// assert!(cache_miss.is_untrusted());
+    /// ```
+    pub fn trusted_or_untrusted(
+        trusted: Option<Trusted>,
+        untrusted: impl FnOnce() -> Insecure,
+    ) -> Self {
+        match trusted {
+            Some(value) => Self::wrap_ok(value),
+            None => Self::wrap_untrusted(untrusted()),
+        }
+    }
+}
+
+impl<Insecure, Trusted: Into<Insecure>> MaybeUntrusted<Insecure, Trusted> {
+    /// Marks the value as untrusted, regardless of whether it was trusted before.
+    /// A trusted value is converted into the insecure type.
+    ///
+    /// This may be used to re-validate a trusted value, for example after it crossed a trust boundary.
+    ///
+    /// ```rust
+    /// use untrusted_value::MaybeUntrusted;
+    ///
+    /// let value = MaybeUntrusted::wrap_ok("x");
+    /// let value: MaybeUntrusted<&str> = value.untrust().into();
+    /// assert!(value.is_untrusted());
+    /// ```
+    pub fn untrust(self) -> UntrustedValue<Insecure> {
+        match self {
+            MaybeUntrusted::Ok(value) => UntrustedValue::wrap(value.into()),
+            MaybeUntrusted::Untrusted(value) => value,
+        }
+    }
+
+    /// Combines two maybe untrusted values into a pair. The pair is only trusted
+    /// if both values are trusted, otherwise both values are considered untrusted.
+    /// When degrading, trusted values are converted into their insecure type.
+    ///
+    /// ```rust
+    /// use untrusted_value::{MaybeUntrusted, SanitizeWith};
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Hostname(String);
+    ///
+    /// impl From<Hostname> for String {
+    ///     fn from(hostname: Hostname) -> Self {
+    ///         hostname.0
+    ///     }
+    /// }
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Port(u16);
+    ///
+    /// impl From<Port> for u32 {
+    ///     fn from(port: Port) -> Self {
+    ///         port.0.into()
+    ///     }
+    /// }
+    ///
+    /// let trusted_host = || MaybeUntrusted::<String, Hostname>::wrap_ok(Hostname("localhost".into()));
+    /// let untrusted_host = || MaybeUntrusted::<String, Hostname>::wrap_untrusted("example.com".into());
+    /// let trusted_port = || MaybeUntrusted::<u32, Port>::wrap_ok(Port(80));
+    /// let untrusted_port = || MaybeUntrusted::<u32, Port>::wrap_untrusted(8080);
+    ///
+    /// let pair: MaybeUntrusted<(String, u32), (Hostname, Port)> = trusted_host().zip(trusted_port());
+    /// assert_eq!(
+    ///     pair.sanitize_with(|_| Err(())),
+    ///     Ok((Hostname("localhost".into()), Port(80)))
+    /// );
+    ///
+    /// fn untrusted<Insecure, Trusted>(value: MaybeUntrusted<Insecure, Trusted>) -> Option<Insecure> {
+    ///     match value {
+    ///         MaybeUntrusted::Ok(_) => None,
+    ///         MaybeUntrusted::Untrusted(value) => Some(value.use_untrusted_value()),
+    ///     }
+    /// }
+    ///
+    /// let pair = trusted_host().zip(untrusted_port());
+    /// assert_eq!(untrusted(pair), Some(("localhost".to_string(), 8080)));
+    ///
+    /// let pair = untrusted_host().zip(trusted_port());
+    /// assert_eq!(untrusted(pair), Some(("example.com".to_string(), 80)));
+    ///
+    /// let pair = untrusted_host().zip(untrusted_port());
+    /// assert_eq!(untrusted(pair), Some(("example.com".to_string(), 8080)));
+    /// ```
+    pub fn zip<Other, OtherTrusted: Into<Other>>(
+        self,
+        other: MaybeUntrusted<Other, OtherTrusted>,
+    ) -> MaybeUntrusted<(Insecure, Other), (Trusted, OtherTrusted)> {
+        match (self, other) {
+            (MaybeUntrusted::Ok(value), MaybeUntrusted::Ok(other)) => {
+                MaybeUntrusted::Ok((value, other))
+            }
+            (value, other) => MaybeUntrusted::Untrusted(
+                value
+                    .untrust()
+                    .merge(other.untrust(), |value, other| (value, other)),
+            ),
+        }
+    }
+
+    /// Keeps a trusted value only if it satisfies the predicate, otherwise it is downgraded to
+    /// an untrusted value. Untrusted values stay untrusted, the predicate is not called for them.
+    ///
+    /// This may be used as defense in depth, to re-check trusted values at runtime.
+    ///
+    /// ```rust
+    /// use untrusted_value::MaybeUntrusted;
+    ///
+    /// let port = MaybeUntrusted::<u16>::wrap_ok(8080);
+    /// assert!(port.filter(|port| *port >= 1024).is_ok());
+    ///
+    /// let port = MaybeUntrusted::<u16>::wrap_ok(80);
+    /// assert!(port.filter(|port| *port >= 1024).is_untrusted());
+    ///
+    /// let port = MaybeUntrusted::<u16>::wrap_untrusted(8080);
+    /// assert!(port.filter(|_| true).is_untrusted());
+    /// ```
+    #[must_use]
+    pub fn filter(self, predicate: impl FnOnce(&Trusted) -> bool) -> Self {
+        match self {
+            MaybeUntrusted::Ok(value) if !predicate(&value) => Self::wrap_untrusted(value.into()),
+            value => value,
+        }
+    }
+}
+
+impl<Insecure, Trusted: DefaultSanitizer<Insecure>> MaybeUntrusted<Insecure, Trusted> {
+    /// Sanitizes the value using the [`DefaultSanitizer`] of the trusted type if the value is untrusted.
+    ///
+    /// # Errors
+    /// Returns the sanitizer's error if sanitization fails.
+    ///
+    /// ```rust
+    /// use untrusted_value::{DefaultSanitizer, MaybeUntrusted};
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Port(u16);
+    ///
+    /// impl DefaultSanitizer<&str> for Port {
+    ///     type Error = std::num::ParseIntError;
+    ///
+    ///     fn sanitize_default(value: &str) -> Result<Self, Self::Error> {
+    ///         value.parse().map(Port)
+    ///     }
+    /// }
+    ///
+    /// let port = MaybeUntrusted::<&str, Port>::wrap_ok(Port(80));
+    /// assert_eq!(port.sanitize_default(), Ok(Port(80)));
+    ///
+    /// let port = MaybeUntrusted::<&str, Port>::wrap_untrusted("8080");
+    /// assert_eq!(port.sanitize_default(), Ok(Port(8080)));
+    /// ```
+    pub fn sanitize_default(self) -> Result<Trusted, Trusted::Error> {
+        match self {
+            MaybeUntrusted::Ok(value) => Ok(value),
+            MaybeUntrusted::Untrusted(value) => value.sanitize_default(),
+        }
+    }
 }
 
 impl<Insecure, Trusted> SanitizeWith<Insecure, Trusted> for MaybeUntrusted<Insecure, Trusted> {
@@ -79,6 +304,29 @@ impl<Insecure, Trusted> From<UntrustedValue<Insecure>> for MaybeUntrusted<Insecu
     }
 }
 
+/// Formats the trusted value, the untrusted value is redacted.
+///
+/// ```rust
+/// use untrusted_value::MaybeUntrusted;
+///
+/// let trusted = MaybeUntrusted::<&str>::wrap_ok("localhost");
+/// assert_eq!(format!("{trusted:?}"), r#"Ok("localhost")"#);
+///
+/// let untrusted = MaybeUntrusted::<&str>::wrap_untrusted("<script>");
+/// assert_eq!(format!("{untrusted:?}"), "Untrusted(<redacted>)");
+/// ```
+impl<Insecure, Trusted: Debug> Debug for MaybeUntrusted<Insecure, Trusted> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MaybeUntrusted::Ok(value) => f.debug_tuple("Ok").field(value).finish(),
+            MaybeUntrusted::Untrusted(_) => f
+                .debug_tuple("Untrusted")
+                .field(&format_args!("<redacted>"))
+                .finish(),
+        }
+    }
+}
+
 #[allow(clippy::expl_impl_clone_on_copy)]
 impl<Insecure: Clone, Trusted: Clone> Clone for MaybeUntrusted<Insecure, Trusted> {
     /// Clones the value
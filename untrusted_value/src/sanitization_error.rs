@@ -0,0 +1,41 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// Error returned by the common sanitizers of [`UntrustedValue`](crate::UntrustedValue).
+///
+/// New variants may be added by sanitizers of optional features, hence this enum is non-exhaustive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SanitizationError {
+    /// The value is empty, after trimming whitespace
+    Empty,
+    /// The value contains a character that is not an ASCII letter or digit
+    NotAsciiAlphanumeric,
+    /// The path does not exist or cannot be resolved
+    InvalidPath,
+    /// The resolved path is not located within the base directory
+    PathOutsideBase,
+    /// The value does not match the expected value
+    Mismatch,
+    /// The sanitizer panicked
+    Panicked,
+}
+
+impl Display for SanitizationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SanitizationError::Empty => write!(f, "value is empty"),
+            SanitizationError::NotAsciiAlphanumeric => {
+                write!(f, "value contains non ASCII alphanumeric characters")
+            }
+            SanitizationError::InvalidPath => write!(f, "path cannot be resolved"),
+            SanitizationError::PathOutsideBase => {
+                write!(f, "path is not located within the base directory")
+            }
+            SanitizationError::Mismatch => write!(f, "value does not match the expected value"),
+            SanitizationError::Panicked => write!(f, "sanitizer panicked"),
+        }
+    }
+}
+
+impl Error for SanitizationError {}
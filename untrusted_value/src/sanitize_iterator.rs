@@ -0,0 +1,123 @@
+use super::UntrustedValue;
+
+/// Extension trait for iterators over tainted values. Allows sanitizing
+/// all elements using the same sanitizer.
+///
+/// This trait is implemented for all iterators yielding [`UntrustedValue`]s.
+pub trait SanitizeIterator<Insecure>: Iterator<Item = UntrustedValue<Insecure>> + Sized {
+    /// Sanitizes all elements using the provided sanitizer.
+    ///
+    /// Sanitization stops at the first element that fails to sanitize.
+    ///
+    /// # Errors
+    /// Returns the error of the first element that failed to sanitize.
+    ///
+    /// ```rust
+    /// use untrusted_value::{SanitizeIterator, UntrustedValue};
+    ///
+    /// let ports = vec![UntrustedValue::from("80"), UntrustedValue::from("443")];
+    /// let ports: Vec<u16> = ports
+    ///     .into_iter()
+    ///     .sanitize_all(|value| value.parse::<u16>())
+    ///     .expect("Sanitization failed");
+    ///
+    /// assert_eq!(ports, vec![80, 443]);
+    /// ```
+    fn sanitize_all<Trusted, Error, Sanitizer>(
+        self,
+        sanitizer: Sanitizer,
+    ) -> Result<Vec<Trusted>, Error>
+    where
+        Sanitizer: FnMut(Insecure) -> Result<Trusted, Error>,
+    {
+        self.sanitize_all_with_observer(sanitizer, |_, _| {})
+    }
+
+    /// Sanitizes all elements using the provided sanitizer, like [`SanitizeIterator::sanitize_all`].
+    ///
+    /// The observer is called with the index and sanitization result of each element,
+    /// before sanitization stops on an error. This may be used to log which element failed.
+    ///
+    /// # Errors
+    /// Returns the error of the first element that failed to sanitize.
+    ///
+    /// ```rust
+    /// use untrusted_value::{SanitizeIterator, UntrustedValue};
+    ///
+    /// let ports = vec![UntrustedValue::from("80"), UntrustedValue::from("http")];
+    /// let mut failed = Vec::new();
+    ///
+    /// let result = ports.into_iter().sanitize_all_with_observer(
+    ///     |value| value.parse::<u16>(),
+    ///     |index, result| {
+    ///         if result.is_err() {
+    ///             failed.push(index);
+    ///         }
+    ///     },
+    /// );
+    ///
+    /// assert!(result.is_err());
+    /// assert_eq!(failed, vec![1]);
+    /// ```
+    fn sanitize_all_with_observer<Trusted, Error, Sanitizer, Observer>(
+        self,
+        mut sanitizer: Sanitizer,
+        mut observer: Observer,
+    ) -> Result<Vec<Trusted>, Error>
+    where
+        Sanitizer: FnMut(Insecure) -> Result<Trusted, Error>,
+        Observer: FnMut(usize, &Result<Trusted, Error>),
+    {
+        let mut sanitized = Vec::with_capacity(self.size_hint().0);
+
+        for (index, value) in self.enumerate() {
+            let result = value.sanitize_with_mut(&mut sanitizer);
+            observer(index, &result);
+            sanitized.push(result?);
+        }
+
+        Ok(sanitized)
+    }
+
+    /// Lazily sanitizes each element using the provided sanitizer, yielding
+    /// the sanitization result of each element.
+    ///
+    /// In contrast to [`SanitizeIterator::sanitize_all`], elements are only sanitized
+    /// when the returned iterator is advanced.
+    ///
+    /// ```rust
+    /// use untrusted_value::{SanitizeIterator, UntrustedValue};
+    ///
+    /// let ports = vec![
+    ///     UntrustedValue::from("80"),
+    ///     UntrustedValue::from("http"),
+    ///     UntrustedValue::from("443"),
+    /// ];
+    /// let mut sanitized = 0;
+    ///
+    /// let result: Result<Vec<u16>, _> = ports
+    ///     .into_iter()
+    ///     .sanitize_map(|value| {
+    ///         sanitized += 1;
+    ///         value.parse::<u16>()
+    ///     })
+    ///     .collect();
+    ///
+    /// assert!(result.is_err());
+    /// assert_eq!(sanitized, 2);
+    /// ```
+    fn sanitize_map<Trusted, Error, Sanitizer>(
+        self,
+        mut sanitizer: Sanitizer,
+    ) -> impl Iterator<Item = Result<Trusted, Error>>
+    where
+        Sanitizer: FnMut(Insecure) -> Result<Trusted, Error>,
+    {
+        self.map(move |value| value.sanitize_with_mut(&mut sanitizer))
+    }
+}
+
+impl<Insecure, Iter> SanitizeIterator<Insecure> for Iter where
+    Iter: Iterator<Item = UntrustedValue<Insecure>>
+{
+}
@@ -0,0 +1,50 @@
+use super::UntrustedValue;
+use untrusted_value_derive_internals::SanitizeWith;
+
+/// Extension trait for optional tainted values.
+///
+/// This trait is implemented for `Option<UntrustedValue<Insecure>>`.
+pub trait SanitizeOption<Insecure> {
+    /// Sanitizes the contained value using the provided sanitizer if present,
+    /// otherwise returns the trusted default value.
+    ///
+    /// The sanitizer is not called if no value is present.
+    ///
+    /// # Errors
+    /// Returns the sanitizer's error if sanitization fails.
+    ///
+    /// ```rust
+    /// use untrusted_value::{SanitizeOption, UntrustedValue};
+    ///
+    /// let port: Option<UntrustedValue<&str>> = None;
+    /// let port = port.unwrap_or_sanitize(8080, |value| value.parse::<u16>());
+    /// assert_eq!(port, Ok(8080));
+    ///
+    /// let port = Some(UntrustedValue::from("443"));
+    /// let port = port.unwrap_or_sanitize(8080, |value| value.parse::<u16>());
+    /// assert_eq!(port, Ok(443));
+    /// ```
+    fn unwrap_or_sanitize<Trusted, Error, Sanitizer>(
+        self,
+        default: Trusted,
+        sanitizer: Sanitizer,
+    ) -> Result<Trusted, Error>
+    where
+        Sanitizer: FnOnce(Insecure) -> Result<Trusted, Error>;
+}
+
+impl<Insecure> SanitizeOption<Insecure> for Option<UntrustedValue<Insecure>> {
+    fn unwrap_or_sanitize<Trusted, Error, Sanitizer>(
+        self,
+        default: Trusted,
+        sanitizer: Sanitizer,
+    ) -> Result<Trusted, Error>
+    where
+        Sanitizer: FnOnce(Insecure) -> Result<Trusted, Error>,
+    {
+        match self {
+            Some(value) => value.sanitize_with(sanitizer),
+            None => Ok(default),
+        }
+    }
+}
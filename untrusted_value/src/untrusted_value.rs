@@ -1,4 +1,7 @@
-use untrusted_value_derive_internals::{SanitizeValue, SanitizeWith};
+use super::{IntoUntrustedValue, LabeledError};
+use untrusted_value_derive_internals::{
+    DefaultSanitizer, SanitizeValue, SanitizeWith, Sanitizer, Tainted,
+};
 
 /// Represents an untrusted/untrustworthy value.
 /// The data contained inside this type is called tainted.
@@ -15,6 +18,25 @@ use untrusted_value_derive_internals::{SanitizeValue, SanitizeWith};
 ///
 /// For naming purposes an untrusted value mapped inside this type is considered safe/trusted
 /// since it can not be accessed without sanitization.
+///
+/// # Thread safety
+/// Tainting does not restrict sharing a value across threads. The taint is a property of the
+/// value, it stays attached when the value is moved or shared to another thread.
+/// Therefore, `UntrustedValue<T>` is `Send`/`Sync` exactly if `T` is `Send`/`Sync`:
+/// ```rust
+/// use untrusted_value::UntrustedValue;
+///
+/// fn assert_send_sync<T: Send + Sync>() {}
+/// assert_send_sync::<UntrustedValue<String>>();
+/// ```
+///
+/// ```compile_fail
+/// use std::rc::Rc;
+/// use untrusted_value::UntrustedValue;
+///
+/// fn assert_send<T: Send>() {}
+/// assert_send::<UntrustedValue<Rc<()>>>();
+/// ```
 pub struct UntrustedValue<Insecure> {
     value: Insecure,
 }
@@ -30,9 +52,572 @@ impl<Insecure> UntrustedValue<Insecure> {
     }
 
     /// Wraps the provided value as [`UntrustedValue`]
-    pub fn wrap(value: Insecure) -> Self {
+    ///
+    /// This may be used in const contexts:
+    /// ```rust
+    /// use untrusted_value::UntrustedValue;
+    ///
+    /// const DEFAULT_LIMIT: UntrustedValue<u32> = UntrustedValue::wrap(100);
+    /// static LIMITS: [UntrustedValue<u32>; 2] = [DEFAULT_LIMIT, UntrustedValue::wrap(200)];
+    /// # assert_eq!(LIMITS[1].use_untrusted_value(), 200);
+    /// ```
+    pub const fn wrap(value: Insecure) -> Self {
         UntrustedValue { value }
     }
+
+    /// Wraps the provided value as [`UntrustedValue`], unless it already is an `UntrustedValue<Insecure>`.
+    ///
+    /// In contrast to [`UntrustedValue::from`], passing a value that is already tainted
+    /// is a no-op instead of wrapping it twice. See [`IntoUntrustedValue`] for the supported types.
+    ///
+    /// ```rust
+    /// use untrusted_value::UntrustedValue;
+    ///
+    /// let fresh = UntrustedValue::wrap_fresh("alice".to_string());
+    /// let tainted = UntrustedValue::wrap_fresh(UntrustedValue::from("bob".to_string()));
+    ///
+    /// let fresh: UntrustedValue<String> = fresh;
+    /// let tainted: UntrustedValue<String> = tainted;
+    /// # assert_eq!(fresh.use_untrusted_value(), "alice");
+    /// # assert_eq!(tainted.use_untrusted_value(), "bob");
+    /// ```
+    ///
+    /// An already tainted value is never wrapped twice:
+    /// ```compile_fail
+    /// use untrusted_value::UntrustedValue;
+    ///
+    /// let value: UntrustedValue<UntrustedValue<i32>> =
+    ///     UntrustedValue::wrap_fresh(UntrustedValue::from(42)); // error: mismatched types
+    /// ```
+    pub fn wrap_fresh<Value>(value: Value) -> Self
+    where
+        Value: IntoUntrustedValue<Insecure = Insecure>,
+    {
+        value.into_untrusted_value()
+    }
+
+    /// Borrows the tainted value. The returned reference stays tainted.
+    ///
+    /// ```rust
+    /// use untrusted_value::{SanitizeWith, UntrustedValue};
+    ///
+    /// let name = UntrustedValue::from("alice".to_string());
+    /// let length = name
+    ///     .as_ref()
+    ///     .sanitize_with(|name| Ok::<usize, ()>(name.len()))
+    ///     .expect("Sanitization failed");
+    /// assert_eq!(length, 5);
+    /// # let _: UntrustedValue<String> = name;
+    /// ```
+    pub fn as_ref(&self) -> UntrustedValue<&Insecure> {
+        UntrustedValue::wrap(&self.value)
+    }
+
+    /// Sanitizes a borrowed view of the tainted value, returning a trusted reference into it.
+    ///
+    /// Sanitizers passed to [`SanitizeWith::sanitize_with`] consume the value, so they can not return
+    /// a borrow of it. Using this method, the owned value stays tainted inside `self`, while the
+    /// sanitized view, like a sub-slice, is tied to its lifetime. This avoids copying the validated part.
+    /// Convert the view to an owned value if it must outlive `self`.
+    ///
+    /// # Errors
+    /// Returns the sanitizer's error if sanitization fails.
+    ///
+    /// ```rust
+    /// use untrusted_value::UntrustedValue;
+    ///
+    /// fn sanitize_name(name: &String) -> Result<&str, ()> {
+    ///     let name = name.trim();
+    ///     if !name.is_empty() && name.chars().all(char::is_alphanumeric) {
+    ///         Ok(name)
+    ///     } else {
+    ///         Err(())
+    ///     }
+    /// }
+    ///
+    /// let name = UntrustedValue::from("  alice\n".to_string());
+    /// let trusted: &str = name.sanitize_borrowed(sanitize_name).expect("Sanitization failed");
+    /// assert_eq!(trusted, "alice");
+    ///
+    /// let name = UntrustedValue::from(" <script> ".to_string());
+    /// assert!(name.sanitize_borrowed(sanitize_name).is_err());
+    /// ```
+    pub fn sanitize_borrowed<'a, Trusted, Error, Sanitizer>(
+        &'a self,
+        sanitizer: Sanitizer,
+    ) -> Result<&'a Trusted, Error>
+    where
+        Trusted: ?Sized,
+        Sanitizer: FnOnce(&'a Insecure) -> Result<&'a Trusted, Error>,
+    {
+        sanitizer(&self.value)
+    }
+
+    /// Always returns true, since the contained value is tainted.
+    /// See also the [`Tainted`] trait.
+    pub const fn is_tainted(&self) -> bool {
+        Self::TAINTED
+    }
+
+    /// Combines this tainted value with another tainted value using the provided function.
+    ///
+    /// Since both inputs are tainted, the result is tainted as well.
+    ///
+    /// ```rust
+    /// use untrusted_value::UntrustedValue;
+    ///
+    /// let first_name = UntrustedValue::from("Jane".to_string());
+    /// let last_name = UntrustedValue::from("Doe".to_string());
+    ///
+    /// let full_name: UntrustedValue<String> =
+    ///     first_name.merge(last_name, |first, last| format!("{first} {last}"));
+    /// # assert_eq!(full_name.use_untrusted_value(), "Jane Doe");
+    /// ```
+    pub fn merge<Other, Merged, Merger>(
+        self,
+        other: UntrustedValue<Other>,
+        merger: Merger,
+    ) -> UntrustedValue<Merged>
+    where
+        Merger: FnOnce(Insecure, Other) -> Merged,
+    {
+        UntrustedValue::wrap(merger(self.value, other.value))
+    }
+
+    /// Sanitizes the value using the provided sanitizer. If sanitization fails,
+    /// the provided default value is returned instead.
+    ///
+    /// The default value is returned as trusted value, the caller is responsible
+    /// for ensuring that the default value is safe to use.
+    ///
+    /// ```rust
+    /// use untrusted_value::UntrustedValue;
+    ///
+    /// let page_size = UntrustedValue::from("abc");
+    /// let page_size: u32 = page_size.sanitize_with_fallback(|value| value.parse(), 20);
+    /// assert_eq!(page_size, 20);
+    ///
+    /// let page_size = UntrustedValue::from("50");
+    /// let page_size: u32 = page_size.sanitize_with_fallback(|value| value.parse(), 20);
+    /// assert_eq!(page_size, 50);
+    /// ```
+    pub fn sanitize_with_fallback<Trusted, Error, Sanitizer>(
+        self,
+        sanitizer: Sanitizer,
+        default: Trusted,
+    ) -> Trusted
+    where
+        Sanitizer: FnOnce(Insecure) -> Result<Trusted, Error>,
+    {
+        sanitizer(self.value).unwrap_or(default)
+    }
+
+    /// Tries the provided sanitizers in order and returns the result of the first
+    /// sanitizer that succeeds. Returns `None` if all sanitizers fail.
+    ///
+    /// This is useful for input that may be in one of several valid formats.
+    /// Since multiple sanitizers are tried, each sanitizer only borrows the value.
+    ///
+    /// In contrast to [`UntrustedValue::check`], this is not gated behind the `inspect_untrusted` feature:
+    /// like [`SanitizeWith::sanitize_with`], the value is consumed and only a sanitizer's result is returned,
+    /// hence the tainted value can not be observed without passing a sanitizer.
+    ///
+    /// ```rust
+    /// use untrusted_value::UntrustedValue;
+    ///
+    /// fn parse_iso(value: &&str) -> Option<(u32, u32, u32)> {
+    ///     let mut parts = value.split('-').map(|part| part.parse().ok());
+    ///     Some((parts.next()??, parts.next()??, parts.next()??))
+    /// }
+    ///
+    /// fn parse_german(value: &&str) -> Option<(u32, u32, u32)> {
+    ///     let mut parts = value.split('.').rev().map(|part| part.parse().ok());
+    ///     Some((parts.next()??, parts.next()??, parts.next()??))
+    /// }
+    ///
+    /// let date = UntrustedValue::from("20.07.2024");
+    /// let date = date.sanitize_any(&[&parse_iso, &parse_german]);
+    /// assert_eq!(date, Some((2024, 7, 20)));
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn sanitize_any<Trusted>(
+        self,
+        sanitizers: &[&dyn Fn(&Insecure) -> Option<Trusted>],
+    ) -> Option<Trusted> {
+        sanitizers
+            .iter()
+            .find_map(|sanitizer| sanitizer(&self.value))
+    }
+
+    /// Sanitizes the value using the provided stateful sanitizer.
+    ///
+    /// In contrast to [`SanitizeWith::sanitize_with`], which consumes an `FnOnce` sanitizer,
+    /// the sanitizer is only borrowed. Hence, it may be reused for multiple values and keep
+    /// state between calls, for example to reject duplicates within a batch.
+    ///
+    /// # Errors
+    /// Returns the sanitizer's error if sanitization fails.
+    ///
+    /// ```rust
+    /// use std::collections::HashSet;
+    /// use untrusted_value::UntrustedValue;
+    ///
+    /// let mut seen = HashSet::new();
+    /// let mut unique = |name: &'static str| {
+    ///     if seen.insert(name) { Ok(name) } else { Err(name) }
+    /// };
+    ///
+    /// let names = ["alice", "bob", "alice"].map(UntrustedValue::from);
+    /// let names: Vec<_> = names
+    ///     .into_iter()
+    ///     .map(|name| name.sanitize_with_mut(&mut unique))
+    ///     .collect();
+    ///
+    /// assert_eq!(names, vec![Ok("alice"), Ok("bob"), Err("alice")]);
+    /// ```
+    pub fn sanitize_with_mut<Trusted, Error, Sanitizer>(
+        self,
+        sanitizer: &mut Sanitizer,
+    ) -> Result<Trusted, Error>
+    where
+        Sanitizer: FnMut(Insecure) -> Result<Trusted, Error>,
+    {
+        sanitizer(self.value)
+    }
+
+    /// Sanitizes the value into a caller-owned buffer. The sanitizer writes the sanitized
+    /// bytes into the buffer, such that the buffer may be reused across multiple values.
+    ///
+    /// The buffer is not cleared before calling the sanitizer. If sanitization fails,
+    /// the buffer may contain partially written data.
+    ///
+    /// # Errors
+    /// Returns the sanitizer's error if sanitization fails.
+    ///
+    /// ```rust
+    /// use untrusted_value::UntrustedValue;
+    ///
+    /// fn printable(value: &[u8], buf: &mut Vec<u8>) -> Result<(), u8> {
+    ///     for &byte in value {
+    ///         if !byte.is_ascii_graphic() {
+    ///             return Err(byte);
+    ///         }
+    ///         buf.push(byte);
+    ///     }
+    ///     Ok(())
+    /// }
+    ///
+    /// let mut buf = Vec::with_capacity(16);
+    ///
+    /// UntrustedValue::from(b"hello".as_slice()).sanitize_into_buf(&mut buf, printable)?;
+    /// assert_eq!(buf, b"hello");
+    ///
+    /// buf.clear();
+    /// UntrustedValue::from(b"world".as_slice()).sanitize_into_buf(&mut buf, printable)?;
+    /// assert_eq!(buf, b"world");
+    ///
+    /// let result = UntrustedValue::from(b"\x00".as_slice()).sanitize_into_buf(&mut buf, printable);
+    /// assert_eq!(result, Err(0));
+    /// # Ok::<(), u8>(())
+    /// ```
+    pub fn sanitize_into_buf<Error, Sanitizer>(
+        self,
+        buf: &mut Vec<u8>,
+        sanitizer: Sanitizer,
+    ) -> Result<(), Error>
+    where
+        Sanitizer: FnOnce(Insecure, &mut Vec<u8>) -> Result<(), Error>,
+    {
+        sanitizer(self.value, buf)
+    }
+
+    /// Sanitizes the value using the provided sanitizer, like [`SanitizeWith::sanitize_with`],
+    /// converting the sanitizer's error into the caller's error type using [`From`].
+    ///
+    /// # Errors
+    /// Returns the converted error of the sanitizer if sanitization fails.
+    ///
+    /// ```rust
+    /// use untrusted_value::UntrustedValue;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// enum RequestError {
+    ///     InvalidPort,
+    /// }
+    ///
+    /// impl From<()> for RequestError {
+    ///     fn from(_: ()) -> Self {
+    ///         RequestError::InvalidPort
+    ///     }
+    /// }
+    ///
+    /// fn sanitize_port(port: &str) -> Result<u16, ()> {
+    ///     port.parse().map_err(|_| ())
+    /// }
+    ///
+    /// let port = UntrustedValue::from("http");
+    /// let port: Result<u16, RequestError> = port.sanitize_with_err_into(sanitize_port);
+    /// assert_eq!(port, Err(RequestError::InvalidPort));
+    /// ```
+    pub fn sanitize_with_err_into<Trusted, Error, Sanitizer, IntoError>(
+        self,
+        sanitizer: Sanitizer,
+    ) -> Result<Trusted, IntoError>
+    where
+        Sanitizer: FnOnce(Insecure) -> Result<Trusted, Error>,
+        IntoError: From<Error>,
+    {
+        sanitizer(self.value).map_err(IntoError::from)
+    }
+
+    /// Sanitizes the value using the provided sanitizer, like [`SanitizeWith::sanitize_with`].
+    ///
+    /// On failure, the error is labeled with the given label, e.g. the name of the
+    /// untrusted source. This helps to identify which value failed when sanitizing multiple values.
+    ///
+    /// # Errors
+    /// Returns the sanitizer's error, labeled with `label`.
+    ///
+    /// ```rust
+    /// use untrusted_value::UntrustedValue;
+    ///
+    /// let port = UntrustedValue::from("http");
+    /// let error = port
+    ///     .sanitize_with_labeled("port", |value| value.parse::<u16>())
+    ///     .expect_err("Sanitization succeeded");
+    ///
+    /// assert_eq!(error.label, "port");
+    /// assert_eq!(error.to_string(), "failed to sanitize `port`");
+    /// ```
+    pub fn sanitize_with_labeled<Trusted, Error, Sanitizer>(
+        self,
+        label: &'static str,
+        sanitizer: Sanitizer,
+    ) -> Result<Trusted, LabeledError<Error>>
+    where
+        Sanitizer: FnOnce(Insecure) -> Result<Trusted, Error>,
+    {
+        sanitizer(self.value).map_err(|error| LabeledError { label, error })
+    }
+
+    /// Sanitizes the value using the [`DefaultSanitizer`] of the trusted type.
+    ///
+    /// # Errors
+    /// Returns the sanitizer's error if sanitization fails.
+    ///
+    /// ```rust
+    /// use untrusted_value::{DefaultSanitizer, UntrustedValue};
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Username(String);
+    ///
+    /// impl DefaultSanitizer<&str> for Username {
+    ///     type Error = ();
+    ///
+    ///     fn sanitize_default(value: &str) -> Result<Self, Self::Error> {
+    ///         if value.chars().all(|c| c.is_ascii_alphanumeric()) {
+    ///             Ok(Username(value.to_string()))
+    ///         } else {
+    ///             Err(())
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let name = UntrustedValue::from("alice");
+    /// assert_eq!(name.sanitize_default(), Ok(Username("alice".to_string())));
+    ///
+    /// let name = UntrustedValue::from("<script>");
+    /// assert_eq!(name.sanitize_default::<Username>(), Err(()));
+    /// ```
+    pub fn sanitize_default<Trusted>(self) -> Result<Trusted, Trusted::Error>
+    where
+        Trusted: DefaultSanitizer<Insecure>,
+    {
+        Trusted::sanitize_default(self.value)
+    }
+
+    /// Sanitizes the value using the provided [`Sanitizer`] object.
+    ///
+    /// # Errors
+    /// If the sanitization fails, the error of the sanitizer is returned.
+    ///
+    /// ```rust
+    /// use untrusted_value::{Sanitizer, UntrustedValue};
+    ///
+    /// struct PortSanitizer {
+    ///     allow_privileged: bool,
+    /// }
+    ///
+    /// impl Sanitizer<u32, u16> for PortSanitizer {
+    ///     type Error = ();
+    ///
+    ///     fn sanitize(&self, value: u32) -> Result<u16, Self::Error> {
+    ///         match u16::try_from(value) {
+    ///             Ok(port) if port >= 1024 || self.allow_privileged => Ok(port),
+    ///             _ => Err(()),
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let sanitizer: &dyn Sanitizer<u32, u16, Error = ()> = &PortSanitizer {
+    ///     allow_privileged: false,
+    /// };
+    ///
+    /// assert_eq!(UntrustedValue::from(8080).sanitize_into(sanitizer), Ok(8080));
+    /// assert_eq!(UntrustedValue::from(80).sanitize_into(sanitizer), Err(()));
+    /// assert_eq!(UntrustedValue::from(100_000).sanitize_into(sanitizer), Err(()));
+    /// ```
+    pub fn sanitize_into<Trusted, S>(self, sanitizer: &S) -> Result<Trusted, S::Error>
+    where
+        S: Sanitizer<Insecure, Trusted> + ?Sized,
+    {
+        sanitizer.sanitize(self.value)
+    }
+}
+
+#[cfg(feature = "inspect_untrusted")]
+impl<Insecure> UntrustedValue<Insecure> {
+    /// Checks the tainted value against the provided predicate, without removing the taint.
+    ///
+    /// This may be used for routing decisions, while the value stays tainted for later sanitization.
+    /// Note that the predicate has access to the tainted value, it must not leak the value.
+    ///
+    /// ```rust
+    /// use untrusted_value::UntrustedValue;
+    ///
+    /// let comment = UntrustedValue::from("Hello World!".to_string());
+    ///
+    /// assert!(comment.check(|value| value.len() <= 280));
+    /// let comment: UntrustedValue<String> = comment;
+    /// ```
+    pub fn check<Predicate>(&self, predicate: Predicate) -> bool
+    where
+        Predicate: FnOnce(&Insecure) -> bool,
+    {
+        predicate(&self.value)
+    }
+
+    /// Returns a tainted reference to the element at the given index, or `None` if the
+    /// index is out of bounds. The returned element stays tainted.
+    ///
+    /// ```rust
+    /// use untrusted_value::{SanitizeWith, UntrustedValue};
+    ///
+    /// let names = UntrustedValue::from(vec!["alice".to_string(), "bob".to_string()]);
+    ///
+    /// let name: UntrustedValue<&String> = names.get_untrusted(1).expect("Index out of bounds");
+    /// let name = name
+    ///     .sanitize_with(|name| Ok::<&str, ()>(name.as_str()))
+    ///     .expect("Sanitization failed");
+    /// assert_eq!(name, "bob");
+    ///
+    /// assert!(names.get_untrusted(2).is_none());
+    /// ```
+    pub fn get_untrusted<Element>(&self, index: usize) -> Option<UntrustedValue<&Element>>
+    where
+        Insecure: AsRef<[Element]>,
+    {
+        self.value.as_ref().get(index).map(UntrustedValue::wrap)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<Insecure> UntrustedValue<Insecure> {
+    /// Sanitizes the value using the provided asynchronous sanitizer, like [`SanitizeWith::sanitize_with`].
+    ///
+    /// This may be used for sanitizers that must await, for example checking
+    /// the value against a remote allow-list.
+    ///
+    /// # Errors
+    /// Returns the sanitizer's error if sanitization fails.
+    ///
+    /// ```rust
+    /// use untrusted_value::UntrustedValue;
+    ///
+    /// async fn is_allowed(user: &str) -> bool {
+    ///     user == "alice"
+    /// }
+    ///
+    /// #[tokio::main(flavor = "current_thread")]
+    /// async fn main() {
+    ///     let user = UntrustedValue::from("alice");
+    ///     let user = user
+    ///         .sanitize_with_async(|user| async move {
+    ///             if is_allowed(user).await { Ok(user) } else { Err(()) }
+    ///         })
+    ///         .await;
+    ///     assert_eq!(user, Ok("alice"));
+    /// }
+    /// ```
+    pub async fn sanitize_with_async<Trusted, Error, Sanitizer, SanitizerFuture>(
+        self,
+        sanitizer: Sanitizer,
+    ) -> Result<Trusted, Error>
+    where
+        Sanitizer: FnOnce(Insecure) -> SanitizerFuture,
+        SanitizerFuture: std::future::Future<Output = Result<Trusted, Error>>,
+    {
+        sanitizer(self.value).await
+    }
+}
+
+impl<Insecure> UntrustedValue<UntrustedValue<Insecure>> {
+    /// Removes one level of wrapping. Wrapping a tainted value again does not add
+    /// any additional taint, hence the result is still tainted.
+    ///
+    /// ```rust
+    /// use untrusted_value::UntrustedValue;
+    ///
+    /// let value = UntrustedValue::from(UntrustedValue::from(42));
+    /// let value: UntrustedValue<i32> = value.flatten();
+    /// # assert_eq!(value.use_untrusted_value(), 42);
+    /// ```
+    pub fn flatten(self) -> UntrustedValue<Insecure> {
+        self.value
+    }
+}
+
+impl<Insecure> UntrustedValue<Box<Insecure>> {
+    /// Sanitizes the boxed value using the [`SanitizeValue`] implementation of the unboxed value.
+    ///
+    /// Deriving `SanitizeValue` already implements `SanitizeValue<Box<Trusted>>` for the boxed type.
+    /// For hand-written implementations this is the route to sanitize boxed values, for example
+    /// in recursive types.
+    ///
+    /// # Errors
+    /// Returns the error of the unboxed value's sanitizer if sanitization fails.
+    ///
+    /// ```rust
+    /// use untrusted_value::{SanitizeValue, UntrustedValue};
+    ///
+    /// struct Port(u16);
+    ///
+    /// impl SanitizeValue<Port> for UntrustedValue<Port> {
+    ///     type Error = ();
+    ///
+    ///     fn sanitize_value(self) -> Result<Port, Self::Error> {
+    ///         let port = self.use_untrusted_value();
+    ///         if port.0 >= 1024 { Ok(port) } else { Err(()) }
+    ///     }
+    /// }
+    ///
+    /// let port = UntrustedValue::from(Box::new(Port(8080)));
+    /// let port: Box<Port> = port.sanitize_boxed().expect("Sanitization failed");
+    /// assert_eq!(port.0, 8080);
+    ///
+    /// let port = UntrustedValue::from(Box::new(Port(80)));
+    /// assert!(port.sanitize_boxed::<Port>().is_err());
+    /// ```
+    pub fn sanitize_boxed<Trusted>(
+        self,
+    ) -> Result<Box<Trusted>, <UntrustedValue<Insecure> as SanitizeValue<Trusted>>::Error>
+    where
+        UntrustedValue<Insecure>: SanitizeValue<Trusted>,
+    {
+        UntrustedValue::wrap(*self.value)
+            .sanitize_value()
+            .map(Box::new)
+    }
 }
 
 /// Taint can be cleared from the value by using a sanitizer.
@@ -51,6 +636,23 @@ impl<Insecure, Trusted> SanitizeWith<Insecure, Trusted> for UntrustedValue<Insec
 }
 
 /// Provide easy conversion from some value to an [`UntrustedValue`].
+///
+/// Note that the wrapped type is the type of the provided value. Converting a value that is
+/// already an `UntrustedValue<T>` without specifying the target type yields a doubly wrapped
+/// `UntrustedValue<UntrustedValue<T>>`. Use [`UntrustedValue::wrap_fresh`] or specify the target type
+/// `UntrustedValue<T>` to avoid this, the value is then converted as is. Use [`UntrustedValue::flatten`]
+/// to remove the additional wrapping.
+///
+/// ```rust
+/// use untrusted_value::UntrustedValue;
+///
+/// let value = UntrustedValue::from(UntrustedValue::from(42));
+/// let value: UntrustedValue<UntrustedValue<i32>> = value;
+/// let value: UntrustedValue<i32> = value.flatten();
+///
+/// let same: UntrustedValue<i32> = UntrustedValue::from(value);
+/// # assert_eq!(same.use_untrusted_value(), 42);
+/// ```
 impl<Insecure> From<Insecure> for UntrustedValue<Insecure> {
     /// Wraps the provided value as [`UntrustedValue`]
     fn from(value: Insecure) -> Self {
@@ -58,6 +660,73 @@ impl<Insecure> From<Insecure> for UntrustedValue<Insecure> {
     }
 }
 
+/// Collects tainted values into a single tainted [`Vec`]. The taint of the elements
+/// is kept since the whole collection is tainted.
+///
+/// ```rust
+/// use untrusted_value::UntrustedValue;
+///
+/// let names = vec![
+///     UntrustedValue::from("alice".to_string()),
+///     UntrustedValue::from("bob".to_string()),
+///     UntrustedValue::from("eve".to_string()),
+/// ];
+///
+/// let names: UntrustedValue<Vec<String>> = names.into_iter().collect();
+/// # assert_eq!(names.use_untrusted_value(), vec!["alice", "bob", "eve"]);
+/// ```
+impl<Insecure> FromIterator<UntrustedValue<Insecure>> for UntrustedValue<Vec<Insecure>> {
+    fn from_iter<Iter: IntoIterator<Item = UntrustedValue<Insecure>>>(iter: Iter) -> Self {
+        UntrustedValue::wrap(iter.into_iter().map(|value| value.value).collect())
+    }
+}
+
+/// Appends tainted values to a tainted [`Vec`]. The taint of the elements
+/// is kept since the whole collection is tainted.
+///
+/// ```rust
+/// use untrusted_value::UntrustedValue;
+///
+/// let mut names = UntrustedValue::from(vec!["alice".to_string()]);
+/// names.extend([
+///     UntrustedValue::from("bob".to_string()),
+///     UntrustedValue::from("eve".to_string()),
+/// ]);
+///
+/// # assert_eq!(names.use_untrusted_value(), vec!["alice", "bob", "eve"]);
+/// ```
+impl<Insecure> Extend<UntrustedValue<Insecure>> for UntrustedValue<Vec<Insecure>> {
+    fn extend<Iter: IntoIterator<Item = UntrustedValue<Insecure>>>(&mut self, iter: Iter) {
+        self.value.extend(iter.into_iter().map(|value| value.value));
+    }
+}
+
+/// An [`UntrustedValue`] is always tainted.
+///
+/// ```rust
+/// use untrusted_value::{Tainted, UntrustedValue};
+///
+/// struct Hostname(String);
+///
+/// impl Tainted for Hostname {
+///     const TAINTED: bool = false;
+/// }
+///
+/// fn describe<T: Tainted>(_value: &T) -> &'static str {
+///     if T::TAINTED {
+///         "tainted"
+///     } else {
+///         "trusted"
+///     }
+/// }
+///
+/// assert_eq!(describe(&UntrustedValue::from("localhost")), "tainted");
+/// assert_eq!(describe(&Hostname("localhost".into())), "trusted");
+/// ```
+impl<Insecure> Tainted for UntrustedValue<Insecure> {
+    const TAINTED: bool = true;
+}
+
 /// A tainted value may be cloned if the underlying value is cloneable. This is considered safe
 /// since the taint is also cloned.
 #[allow(clippy::expl_impl_clone_on_copy)]
@@ -90,3 +759,23 @@ impl<Sanitized, E, Insecure: SanitizeValue<Sanitized, Error = E>> SanitizeValue<
         self.value.sanitize_value()
     }
 }
+
+#[cfg(feature = "bytes")]
+mod bytes;
+
+#[cfg(feature = "bytemuck")]
+mod bytemuck;
+
+#[cfg(feature = "validator")]
+mod validator;
+
+#[cfg(feature = "catch_panic")]
+mod catch_panic;
+
+#[cfg(feature = "constant_time")]
+mod constant_time;
+
+#[cfg(feature = "std")]
+mod path;
+#[cfg(feature = "std")]
+mod string;
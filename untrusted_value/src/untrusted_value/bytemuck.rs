@@ -0,0 +1,45 @@
+use super::UntrustedValue;
+use ::bytemuck::{Pod, PodCastError};
+
+/// Helpers mirroring the [`bytemuck`] API, such that tainted plain old data can be
+/// reinterpreted without removing the taint.
+///
+/// The reinterpreted values are tainted as well.
+impl<Insecure: Pod> UntrustedValue<Insecure> {
+    /// Reinterprets the tainted value as a value of another type of the same size.
+    /// See [`bytemuck::cast`].
+    ///
+    /// # Panics
+    /// Panics if the types have a different size.
+    ///
+    /// ```rust
+    /// use untrusted_value::UntrustedValue;
+    ///
+    /// let header = UntrustedValue::from([0x01u8, 0x00, 0x00, 0x00]);
+    /// let header: UntrustedValue<u32> = header.cast();
+    /// assert_eq!(
+    ///     header.use_untrusted_value(),
+    ///     u32::from_ne_bytes([0x01, 0x00, 0x00, 0x00])
+    /// );
+    /// ```
+    #[must_use]
+    pub fn cast<Target: Pod>(self) -> UntrustedValue<Target> {
+        UntrustedValue::wrap(::bytemuck::cast(self.value))
+    }
+
+    /// Reinterprets the tainted value as a value of another type of the same size,
+    /// like [`UntrustedValue::cast`]. See [`bytemuck::try_cast`].
+    ///
+    /// # Errors
+    /// Returns an error if the types have a different size.
+    ///
+    /// ```rust
+    /// use untrusted_value::UntrustedValue;
+    ///
+    /// let header = UntrustedValue::from([0x01u8, 0x00]);
+    /// assert!(header.try_cast::<u32>().is_err());
+    /// ```
+    pub fn try_cast<Target: Pod>(self) -> Result<UntrustedValue<Target>, PodCastError> {
+        ::bytemuck::try_cast(self.value).map(UntrustedValue::wrap)
+    }
+}
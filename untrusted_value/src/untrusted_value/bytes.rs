@@ -0,0 +1,52 @@
+use super::UntrustedValue;
+use ::bytes::Bytes;
+use std::ops::RangeBounds;
+
+/// Helpers mirroring the [`Bytes`] API, such that tainted buffers can be split
+/// and sliced without removing the taint.
+///
+/// The returned sub-buffers are tainted as well.
+impl UntrustedValue<Bytes> {
+    /// Splits the tainted buffer into two at the given index.
+    ///
+    /// Afterward `self` contains elements `[at, len)`, and the returned
+    /// [`UntrustedValue`] contains elements `[0, at)`. See [`Bytes::split_to`].
+    ///
+    /// # Panics
+    /// Panics if `at > len`.
+    ///
+    /// ```rust
+    /// use bytes::Bytes;
+    /// use untrusted_value::UntrustedValue;
+    ///
+    /// let mut tail = UntrustedValue::from(Bytes::from_static(b"GET /index"));
+    /// let head: UntrustedValue<Bytes> = tail.split_to_untrusted(4);
+    ///
+    /// assert_eq!(head.use_untrusted_value(), Bytes::from_static(b"GET "));
+    /// assert_eq!(tail.use_untrusted_value(), Bytes::from_static(b"/index"));
+    /// ```
+    #[must_use]
+    pub fn split_to_untrusted(&mut self, at: usize) -> UntrustedValue<Bytes> {
+        UntrustedValue::wrap(self.value.split_to(at))
+    }
+
+    /// Returns a tainted slice of the buffer for the provided range.
+    /// See [`Bytes::slice`].
+    ///
+    /// # Panics
+    /// Panics if the range is out of bounds.
+    ///
+    /// ```rust
+    /// use bytes::Bytes;
+    /// use untrusted_value::UntrustedValue;
+    ///
+    /// let request = UntrustedValue::from(Bytes::from_static(b"GET /index"));
+    /// let path: UntrustedValue<Bytes> = request.slice_untrusted(4..);
+    ///
+    /// assert_eq!(path.use_untrusted_value(), Bytes::from_static(b"/index"));
+    /// ```
+    #[must_use]
+    pub fn slice_untrusted(&self, range: impl RangeBounds<usize>) -> UntrustedValue<Bytes> {
+        UntrustedValue::wrap(self.value.slice(range))
+    }
+}
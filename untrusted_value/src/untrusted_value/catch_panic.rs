@@ -0,0 +1,51 @@
+use super::UntrustedValue;
+use crate::SanitizationError;
+use std::panic::{catch_unwind, UnwindSafe};
+
+impl<Insecure: UnwindSafe> UntrustedValue<Insecure> {
+    /// Sanitizes the value using the provided sanitizer, like [`crate::SanitizeWith::sanitize_with`].
+    /// If the sanitizer panics, the panic is caught and [`SanitizationError::Panicked`] is returned.
+    ///
+    /// This prevents malformed tainted values from crashing the program, when using third party
+    /// sanitizers that may panic on unexpected input.
+    ///
+    /// Both the value and the sanitizer must be [`UnwindSafe`]. If the sanitizer captures mutable
+    /// references or types with interior mutability, wrap it in [`std::panic::AssertUnwindSafe`] after making
+    /// sure that no broken invariants are observable once a panic was caught.
+    /// Panics are only caught if the program is compiled with `panic = "unwind"`, and the panic hook,
+    /// which prints the panic message by default, is still called.
+    ///
+    /// # Errors
+    /// Returns the sanitizer's error if sanitization fails, or [`SanitizationError::Panicked`]
+    /// converted into the sanitizer's error type if the sanitizer panics.
+    ///
+    /// ```rust
+    /// use untrusted_value::{SanitizationError, UntrustedValue};
+    ///
+    /// fn sanitize_length(value: Vec<u8>) -> Result<u8, SanitizationError> {
+    ///     // panics on empty input
+    ///     Ok(value[0])
+    /// }
+    ///
+    /// let value = UntrustedValue::from(vec![4, 1, 2, 3, 4]);
+    /// assert_eq!(value.sanitize_with_catch(sanitize_length), Ok(4));
+    ///
+    /// let value = UntrustedValue::from(Vec::new());
+    /// assert_eq!(
+    ///     value.sanitize_with_catch(sanitize_length),
+    ///     Err(SanitizationError::Panicked)
+    /// );
+    /// ```
+    pub fn sanitize_with_catch<Trusted, Error, Sanitizer>(
+        self,
+        sanitizer: Sanitizer,
+    ) -> Result<Trusted, Error>
+    where
+        Sanitizer: FnOnce(Insecure) -> Result<Trusted, Error> + UnwindSafe,
+        Error: From<SanitizationError>,
+    {
+        let value = self.value;
+        catch_unwind(move || sanitizer(value))
+            .unwrap_or_else(|_| Err(SanitizationError::Panicked.into()))
+    }
+}
@@ -0,0 +1,36 @@
+use super::UntrustedValue;
+use crate::SanitizationError;
+use ::subtle::ConstantTimeEq;
+
+impl UntrustedValue<Vec<u8>> {
+    /// Accepts the value only if it is equal to `expected`, like a token or password.
+    ///
+    /// The comparison is done in constant time using [`subtle`], such that the duration of the comparison
+    /// does not reveal how many leading bytes of the value match `expected`. Note that the length of
+    /// `expected` is not hidden.
+    ///
+    /// # Errors
+    /// Returns [`SanitizationError::Mismatch`] if the value is not equal to `expected`.
+    ///
+    /// ```rust
+    /// use untrusted_value::{SanitizationError, UntrustedValue};
+    ///
+    /// let secret = b"s3cr3t-t0ken";
+    ///
+    /// let token = UntrustedValue::from(b"s3cr3t-t0ken".to_vec());
+    /// assert_eq!(token.sanitize_eq(secret), Ok(secret.to_vec()));
+    ///
+    /// let token = UntrustedValue::from(b"s3cr3t-guess".to_vec());
+    /// assert_eq!(token.sanitize_eq(secret), Err(SanitizationError::Mismatch));
+    ///
+    /// let token = UntrustedValue::from(b"s3cr3t".to_vec());
+    /// assert_eq!(token.sanitize_eq(secret), Err(SanitizationError::Mismatch));
+    /// ```
+    pub fn sanitize_eq(self, expected: &[u8]) -> Result<Vec<u8>, SanitizationError> {
+        if bool::from(self.value.as_slice().ct_eq(expected)) {
+            Ok(self.value)
+        } else {
+            Err(SanitizationError::Mismatch)
+        }
+    }
+}
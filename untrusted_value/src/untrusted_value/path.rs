@@ -0,0 +1,53 @@
+use super::UntrustedValue;
+use crate::SanitizationError;
+use std::path::{Path, PathBuf};
+
+impl UntrustedValue<PathBuf> {
+    /// Resolves the path relative to `base` and accepts it only if the resolved path
+    /// is located within `base`.
+    ///
+    /// Both paths are canonicalized, so `..` components and symbolic links cannot be used
+    /// to escape `base`. Absolute paths are accepted only if they point into `base`.
+    /// Since canonicalization requires the path to exist, this sanitizer is meant for
+    /// accessing existing files, it cannot be used to validate paths of files to be created.
+    ///
+    /// # Errors
+    /// Returns [`SanitizationError::InvalidPath`] if `base` or the joined path cannot be canonicalized,
+    /// and [`SanitizationError::PathOutsideBase`] if the resolved path is not located within `base`.
+    ///
+    /// ```rust
+    /// use std::path::PathBuf;
+    /// use untrusted_value::{SanitizationError, UntrustedValue};
+    ///
+    /// let base = std::env::temp_dir().join("untrusted_value_sanitize_within");
+    /// std::fs::create_dir_all(base.join("public")).unwrap();
+    /// std::fs::write(base.join("public/index.html"), "").unwrap();
+    ///
+    /// let path = UntrustedValue::from(PathBuf::from("public/index.html"));
+    /// assert_eq!(
+    ///     path.sanitize_within(&base),
+    ///     Ok(base.join("public/index.html").canonicalize().unwrap())
+    /// );
+    ///
+    /// let path = UntrustedValue::from(PathBuf::from("public/../../"));
+    /// assert_eq!(path.sanitize_within(&base), Err(SanitizationError::PathOutsideBase));
+    ///
+    /// let path = UntrustedValue::from(std::env::current_dir().unwrap());
+    /// assert_eq!(path.sanitize_within(&base), Err(SanitizationError::PathOutsideBase));
+    /// ```
+    pub fn sanitize_within(self, base: &Path) -> Result<PathBuf, SanitizationError> {
+        let base = base
+            .canonicalize()
+            .map_err(|_| SanitizationError::InvalidPath)?;
+        let path = base
+            .join(self.value)
+            .canonicalize()
+            .map_err(|_| SanitizationError::InvalidPath)?;
+
+        if path.starts_with(&base) {
+            Ok(path)
+        } else {
+            Err(SanitizationError::PathOutsideBase)
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use super::UntrustedValue;
+use crate::SanitizationError;
+
+/// Common sanitizers for tainted strings. These are building blocks that may be
+/// combined with custom sanitizers, they do not replace validating the value for its purpose.
+impl UntrustedValue<String> {
+    /// Removes leading and trailing whitespace and rejects empty values.
+    ///
+    /// # Errors
+    /// Returns [`SanitizationError::Empty`] if the value is empty after trimming.
+    ///
+    /// ```rust
+    /// use untrusted_value::{SanitizationError, UntrustedValue};
+    ///
+    /// let name = UntrustedValue::from("  alice \n".to_string());
+    /// assert_eq!(name.sanitize_trimmed_nonempty(), Ok("alice".to_string()));
+    ///
+    /// let name = UntrustedValue::from(" \t ".to_string());
+    /// assert_eq!(name.sanitize_trimmed_nonempty(), Err(SanitizationError::Empty));
+    /// ```
+    pub fn sanitize_trimmed_nonempty(self) -> Result<String, SanitizationError> {
+        let trimmed = self.value.trim();
+        if trimmed.is_empty() {
+            Err(SanitizationError::Empty)
+        } else if trimmed.len() == self.value.len() {
+            Ok(self.value)
+        } else {
+            Ok(trimmed.to_string())
+        }
+    }
+
+    /// Accepts the value only if it consists of ASCII letters and digits.
+    /// Empty values are accepted, combine with [`UntrustedValue::sanitize_trimmed_nonempty`] if required.
+    ///
+    /// # Errors
+    /// Returns [`SanitizationError::NotAsciiAlphanumeric`] if the value contains any other character.
+    ///
+    /// ```rust
+    /// use untrusted_value::{SanitizationError, UntrustedValue};
+    ///
+    /// let id = UntrustedValue::from("user42".to_string());
+    /// assert_eq!(id.sanitize_ascii_alphanumeric(), Ok("user42".to_string()));
+    ///
+    /// let id = UntrustedValue::from("../etc/passwd".to_string());
+    /// assert_eq!(
+    ///     id.sanitize_ascii_alphanumeric(),
+    ///     Err(SanitizationError::NotAsciiAlphanumeric)
+    /// );
+    /// ```
+    pub fn sanitize_ascii_alphanumeric(self) -> Result<String, SanitizationError> {
+        if self.value.chars().all(|c| c.is_ascii_alphanumeric()) {
+            Ok(self.value)
+        } else {
+            Err(SanitizationError::NotAsciiAlphanumeric)
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use super::UntrustedValue;
+use ::validator::{Validate, ValidationErrors};
+
+/// Integration with the [`validator`] crate.
+impl<Insecure: Validate> UntrustedValue<Insecure> {
+    /// Validates the tainted value using its [`Validate`] implementation.
+    /// If validation succeeds, the value is returned as trusted value.
+    ///
+    /// # Errors
+    /// Returns the validation errors if validation fails.
+    ///
+    /// ```rust
+    /// use untrusted_value::UntrustedValue;
+    /// use validator::Validate;
+    ///
+    /// #[derive(Validate)]
+    /// struct SignupData {
+    ///     #[validate(length(min = 1))]
+    ///     name: String,
+    /// }
+    ///
+    /// let signup = UntrustedValue::from(SignupData { name: "alice".to_string() });
+    /// let signup = signup.sanitize_validated().expect("Validation failed");
+    /// assert_eq!(signup.name, "alice");
+    ///
+    /// let signup = UntrustedValue::from(SignupData { name: String::new() });
+    /// let errors = signup.sanitize_validated().err().expect("Validation succeeded");
+    /// assert!(errors.field_errors().contains_key("name"));
+    /// ```
+    pub fn sanitize_validated(self) -> Result<Insecure, ValidationErrors> {
+        self.value.validate()?;
+        Ok(self.value)
+    }
+}
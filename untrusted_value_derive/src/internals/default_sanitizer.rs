@@ -0,0 +1,17 @@
+/// The default sanitizer of a trusted type, that sanitizes values of type `Insecure` to `Self`.
+///
+/// Implementing this trait registers an application-wide sanitizer for the trusted type,
+/// such that values can be sanitized without passing a sanitizer each time,
+/// see `UntrustedValue::sanitize_default` and `MaybeUntrusted::sanitize_default`.
+///
+/// The `sanitize_default` function SHOULD clear all taint from the input.
+pub trait DefaultSanitizer<Insecure>: Sized {
+    /// The error type that is returned in case of a sanitization failure.
+    type Error;
+
+    /// Sanitizes the value.
+    ///
+    /// # Errors
+    /// If the sanitization fails
+    fn sanitize_default(value: Insecure) -> Result<Self, Self::Error>;
+}
@@ -6,3 +6,15 @@ pub use sanitize_value::*;
 
 mod sanitize_with;
 pub use sanitize_with::*;
+
+mod tainted;
+pub use tainted::*;
+
+mod sanitizer;
+pub use sanitizer::*;
+
+mod default_sanitizer;
+pub use default_sanitizer::*;
+
+mod sanitize_value_with_report;
+pub use sanitize_value_with_report::*;
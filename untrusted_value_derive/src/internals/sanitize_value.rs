@@ -4,6 +4,9 @@
 /// If the value cannot be sanitized, an error should be returned.
 ///
 /// The `sanitize_value` function SHOULD clear all taint from the input.
+///
+/// Implementations for boxed values are generated only when deriving `SanitizeValue`.
+/// For hand-written implementations, use `UntrustedValue::sanitize_boxed` to sanitize an `UntrustedValue<Box<T>>`.
 pub trait SanitizeValue<Trusted> {
     /// The error type that is returned in case of a sanitization failure.
     type Error;
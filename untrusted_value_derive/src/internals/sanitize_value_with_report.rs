@@ -0,0 +1,17 @@
+/// The type implementing this trait can be sanitized, while recording details about the
+/// sanitization, like which values were modified or rejected, in a report.
+///
+/// Like `SanitizeValue`, but a mutable report is passed through the sanitization.
+/// The report type is chosen by the caller.
+///
+/// The `sanitize_value_with_report` function SHOULD clear all taint from the input.
+pub trait SanitizeValueWithReport<Trusted, Report: ?Sized> {
+    /// The error type that is returned in case of a sanitization failure.
+    type Error;
+
+    /// Sanitizes the value, recording sanitization details in the report.
+    ///
+    /// # Errors
+    /// If the sanitization fails
+    fn sanitize_value_with_report(self, report: &mut Report) -> Result<Trusted, Self::Error>;
+}
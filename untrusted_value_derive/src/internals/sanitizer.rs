@@ -0,0 +1,16 @@
+/// A reusable sanitizer object, that can sanitize values of type `Insecure` to `Trusted`.
+///
+/// In contrast to the closures passed to `SanitizeWith`, a sanitizer object may be
+/// configured at runtime and passed around as trait object (`&dyn Sanitizer<...>`).
+///
+/// The `sanitize` function SHOULD clear all taint from the input.
+pub trait Sanitizer<Insecure, Trusted> {
+    /// The error type that is returned in case of a sanitization failure.
+    type Error;
+
+    /// Sanitizes the value.
+    ///
+    /// # Errors
+    /// If the sanitization fails
+    fn sanitize(&self, value: Insecure) -> Result<Trusted, Self::Error>;
+}
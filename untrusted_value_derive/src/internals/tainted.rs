@@ -0,0 +1,9 @@
+/// Marks whether values of the implementing type are tainted.
+///
+/// This trait allows generic code to depend on whether a type is tainted at compile time.
+/// It is implemented for `UntrustedValue<T>` with `TAINTED = true`. Types that are known to
+/// contain trusted data may implement this trait with `TAINTED = false`.
+pub trait Tainted {
+    /// True if values of this type are tainted.
+    const TAINTED: bool;
+}
@@ -43,7 +43,12 @@ use syn::{Data, Field, Fields};
 /// - `untrusted_value::SanitizeWith` to sanitize the untrusted variant using a provided sanitizer to its original form
 ///
 /// This proc macro supports the following attributes:
-/// - `#[untrusted_derive(...)]` to implement derive macros for the untrusted variant struct
+/// - `#[untrusted_derive(...)]` to implement derive macros for the untrusted variant struct,
+///   multiple attributes are merged
+/// - `#[untrusted(nested)]` on a field to use the untrusted variant of the field type instead of wrapping it
+///   in `UntrustedValue`, or `#[untrusted(nested = UntrustedType)]` to name the untrusted variant explicitly
+/// - `#[sanitize_with(sanitizer)]` on a field to sanitize it using a function, see below
+/// - `#[sanitize_value(error = ErrorType)]` on the struct to set the error type of the `SanitizeValue` implementation
 ///
 /// # Example
 /// Image the situation where a struct is read from a configuration file using Serde.
@@ -143,19 +148,432 @@ use syn::{Data, Field, Fields};
 /// }
 /// ```
 ///
+/// A field whose type also derives `UntrustedVariant` may be marked with `#[untrusted(nested)]`.
+/// The untrusted variant then contains the untrusted variant of the field type (`NetworkConfigUntrusted`),
+/// instead of `UntrustedValue<NetworkConfig>`. This avoids having to convert nested structs manually:
+/// ```rust
+/// # use untrusted_value::{IntoUntrustedVariant, SanitizeValue};
+/// # use untrusted_value::derive::UntrustedVariant;
+/// #
+/// #[derive(UntrustedVariant)]
+/// #[untrusted_derive(SanitizeValue)]
+/// pub struct GeneralConfig {
+///     #[untrusted(nested)]
+///     pub network: NetworkConfig,
+/// }
+///
+/// #[derive(UntrustedVariant)]
+/// pub struct NetworkConfig {
+///     pub port: u32,
+/// }
+///
+/// impl SanitizeValue<NetworkConfig> for NetworkConfigUntrusted {
+///     type Error = ();
+///
+///     fn sanitize_value(self) -> Result<NetworkConfig, Self::Error> {
+///         Ok(NetworkConfig {
+///             port: self.port.use_untrusted_value(),
+///         })
+///     }
+/// }
+///
+/// let config = GeneralConfig {
+///     network: NetworkConfig { port: 1111 },
+/// }.to_untrusted_variant();
+///
+/// let network: &NetworkConfigUntrusted = &config.network;
+///
+/// let config: GeneralConfig = config.sanitize_value().expect("Sanitization failed");
+/// # assert_eq!(config.network.port, 1111);
+/// ```
+///
+/// The untrusted variant of the field type is named by appending `Untrusted` to the last segment of the
+/// type path. This does not work for type aliases, renamed imports or type parameters. In these cases,
+/// specify the untrusted variant using `#[untrusted(nested = UntrustedType)]`:
+/// ```rust
+/// # use untrusted_value::{IntoUntrustedVariant, SanitizeValue};
+/// # use untrusted_value::derive::UntrustedVariant;
+/// #
+/// #[derive(UntrustedVariant)]
+/// pub struct NetworkConfig {
+///     pub port: u32,
+/// }
+///
+/// type Network = NetworkConfig;
+///
+/// #[derive(UntrustedVariant)]
+/// pub struct GeneralConfig {
+///     #[untrusted(nested = NetworkConfigUntrusted)]
+///     pub network: Network,
+/// }
+///
+/// let config = GeneralConfig {
+///     network: NetworkConfig { port: 1111 },
+/// }.to_untrusted_variant();
+///
+/// let network: NetworkConfigUntrusted = config.network;
+/// # assert_eq!(network.port.use_untrusted_value(), 1111);
+/// ```
+///
+/// For a field of a type parameter, the untrusted variant must be specified:
+/// ```compile_fail
+/// # use untrusted_value::derive::UntrustedVariant;
+/// #
+/// #[derive(UntrustedVariant)]
+/// pub struct Wrapper<T> {
+///     #[untrusted(nested)] // error: specify it using #[untrusted(nested = UntrustedType)]
+///     pub inner: T,
+/// }
+/// ```
+///
+/// Deriving `SanitizeValue` also implements `SanitizeValue<Box<Struct>>` for `UntrustedValue<Box<Struct>>`,
+/// such that boxed fields, like in recursive types, can be sanitized:
+/// ```rust
+/// # use untrusted_value::{IntoUntrustedVariant, SanitizeValue, UntrustedValue};
+/// # use untrusted_value::derive::UntrustedVariant;
+/// #
+/// pub struct Port(u16);
+///
+/// impl SanitizeValue<Port> for Port {
+///     type Error = ();
+///
+///     fn sanitize_value(self) -> Result<Port, Self::Error> {
+///         if self.0 >= 1024 { Ok(self) } else { Err(()) }
+///     }
+/// }
+///
+/// #[derive(UntrustedVariant)]
+/// #[untrusted_derive(SanitizeValue)]
+/// pub struct Listener {
+///     pub port: Port,
+/// }
+///
+/// #[derive(UntrustedVariant)]
+/// #[untrusted_derive(SanitizeValue)]
+/// pub struct Server {
+///     pub listener: Box<Listener>,
+/// }
+///
+/// let server = Server {
+///     listener: Box::new(Listener { port: Port(8080) }),
+/// }.to_untrusted_variant();
+/// let server: Server = server.sanitize_value().expect("Sanitization failed");
+/// assert_eq!(server.listener.port.0, 8080);
+///
+/// let listener = UntrustedValue::from(Box::new(Listener { port: Port(80) }));
+/// let listener: Result<Box<Listener>, ()> = listener.sanitize_value();
+/// assert!(listener.is_err());
+/// ```
+///
+/// Fields already typed `UntrustedValue<_>` are not wrapped again. Like for the `SanitizeValue` derive,
+/// they stay tainted in the sanitized struct:
+/// ```rust
+/// # use untrusted_value::{IntoUntrustedVariant, SanitizeValue, UntrustedValue};
+/// # use untrusted_value::derive::UntrustedVariant;
+/// #
+/// pub struct Port(u16);
+///
+/// impl SanitizeValue<Port> for Port {
+///     type Error = ();
+///
+///     fn sanitize_value(self) -> Result<Port, Self::Error> {
+///         if self.0 >= 1024 { Ok(self) } else { Err(()) }
+///     }
+/// }
+///
+/// #[derive(UntrustedVariant)]
+/// #[untrusted_derive(SanitizeValue)]
+/// pub struct Request {
+///     pub port: Port,
+///     pub comment: UntrustedValue<String>,
+/// }
+///
+/// let request = Request {
+///     port: Port(8080),
+///     comment: UntrustedValue::from("Hello World!".to_string()),
+/// }.to_untrusted_variant();
+/// let comment: &UntrustedValue<String> = &request.comment;
+///
+/// let request: Request = request.sanitize_value().expect("Sanitization failed");
+/// let comment: UntrustedValue<String> = request.comment;
+/// # assert_eq!(comment.use_untrusted_value(), "Hello World!");
+/// ```
+///
+/// Instead of requiring a `SanitizeValue` implementation, a field may be sanitized by a function
+/// using `#[sanitize_with(sanitizer)]`, like for the `SanitizeValue` derive. The sanitizer has the
+/// signature `fn(FieldType) -> Result<FieldType, E>`, where `E: Into<ErrorType>`. The error type of
+/// the `SanitizeValue` implementation must be given by `#[sanitize_value(error = ErrorType)]` on the struct:
+/// ```rust
+/// # use untrusted_value::{IntoUntrustedVariant, SanitizeValue};
+/// # use untrusted_value::derive::UntrustedVariant;
+/// #
+/// #[derive(Debug, PartialEq)]
+/// pub struct InvalidPort;
+///
+/// fn sanitize_port(port: u32) -> Result<u32, InvalidPort> {
+///     if port >= 1024 { Ok(port) } else { Err(InvalidPort) }
+/// }
+///
+/// #[derive(UntrustedVariant)]
+/// #[untrusted_derive(SanitizeValue)]
+/// #[sanitize_value(error = InvalidPort)]
+/// pub struct NetworkConfig {
+///     #[sanitize_with(sanitize_port)]
+///     pub port: u32,
+/// }
+///
+/// let config = NetworkConfig { port: 8080 }.to_untrusted_variant();
+/// let config: NetworkConfig = config.sanitize_value().expect("Sanitization failed");
+/// assert_eq!(config.port, 8080);
+///
+/// let config = NetworkConfig { port: 80 }.to_untrusted_variant();
+/// let config: Result<NetworkConfig, _> = config.sanitize_value();
+/// assert_eq!(config.err(), Some(InvalidPort));
+/// ```
+///
+/// The untrusted variant can not derive `Debug`, since this would print the tainted values:
+/// ```compile_fail
+/// # use untrusted_value::derive::UntrustedVariant;
+/// #
+/// #[derive(UntrustedVariant)]
+/// #[untrusted_derive(Debug)] // error: use RedactedDebug instead
+/// pub struct NetworkConfig {
+///     pub port: u32,
+/// }
+/// ```
+///
+/// The same holds for `Hash`, `PartialEq` and `Eq`, since comparing tainted values would
+/// allow using them without sanitization:
+/// ```compile_fail
+/// # use untrusted_value::derive::UntrustedVariant;
+/// #
+/// #[derive(UntrustedVariant)]
+/// #[untrusted_derive(Hash)] // error: UntrustedValue does not implement Hash
+/// pub struct NetworkConfig {
+///     pub port: u32,
+/// }
+/// ```
+///
+/// Use `#[untrusted_derive(RedactedDebug)]` instead of `Debug`, which implements `Debug` by printing
+/// the field names only:
+/// ```rust
+/// # use untrusted_value::derive::UntrustedVariant;
+/// # use untrusted_value::IntoUntrustedVariant;
+/// #
+/// #[derive(UntrustedVariant)]
+/// #[untrusted_derive(RedactedDebug)]
+/// pub struct NetworkConfig {
+///     pub port: u32,
+///     pub listen_address: String,
+/// }
+///
+/// let config = NetworkConfig {
+///     port: 1111,
+///     listen_address: "0.0.0.0".into(),
+/// }.to_untrusted_variant();
+///
+/// assert_eq!(
+///     format!("{config:?}"),
+///     "NetworkConfigUntrusted { port: <redacted>, listen_address: <redacted> }"
+/// );
+/// ```
+///
+/// Similarly, `#[untrusted_derive(RedactedDisplay)]` implements `Display` by printing each field name
+/// followed by `***`. This allows referencing the untrusted variant in user facing messages:
+/// ```rust
+/// # use untrusted_value::derive::UntrustedVariant;
+/// # use untrusted_value::IntoUntrustedVariant;
+/// #
+/// #[derive(UntrustedVariant)]
+/// #[untrusted_derive(RedactedDisplay)]
+/// pub struct LoginRequest {
+///     pub user: String,
+///     pub password: String,
+/// }
+///
+/// let request = LoginRequest {
+///     user: "alice".into(),
+///     password: "hunter2".into(),
+/// }.to_untrusted_variant();
+///
+/// assert_eq!(
+///     format!("invalid login request: {request}"),
+///     "invalid login request: user: ***, password: ***"
+/// );
+/// ```
+///
+/// Multiple `#[untrusted_derive(...)]` attributes are merged:
+/// ```rust
+/// # use untrusted_value::derive::UntrustedVariant;
+/// # use untrusted_value::IntoUntrustedVariant;
+/// #
+/// #[derive(UntrustedVariant)]
+/// #[untrusted_derive(Clone)]
+/// #[untrusted_derive(RedactedDebug)]
+/// pub struct NetworkConfig {
+///     pub port: u32,
+/// }
+///
+/// let config = NetworkConfig { port: 1111 }.to_untrusted_variant();
+/// assert_eq!(
+///     format!("{:?}", config.clone()),
+///     "NetworkConfigUntrusted { port: <redacted> }"
+/// );
+/// ```
+///
 /// # Panics
 /// This macro will panic if the annotated struct is not valid Rust code.
-#[proc_macro_derive(UntrustedVariant, attributes(untrusted_derive))]
+#[proc_macro_derive(
+    UntrustedVariant,
+    attributes(untrusted_derive, untrusted, sanitize_value, sanitize_with)
+)]
 pub fn untrusted_variant_derive(input: TokenStream) -> TokenStream {
     let ast = syn::parse(input).unwrap();
     untrusted_variant::impl_untrusted_variant_macro(&ast).into()
 }
 
+/// This macro can be used to annotate newtypes, tuple structs with a single field.
+/// Like `UntrustedVariant`, it generates an untrusted variant struct named `<NewtypeName>Untrusted`,
+/// which wraps the field inside `UntrustedValue`.
+///
+/// Additionally, the untrusted variant provides a `sanitize` method, which sanitizes the
+/// wrapped value using a sanitizer function on the field type and returns the newtype:
+/// ```rust
+/// use untrusted_value::derive::UntrustedNewtype;
+/// use untrusted_value::IntoUntrustedVariant;
+///
+/// #[derive(UntrustedNewtype, Debug, PartialEq)]
+/// pub struct Token(String);
+///
+/// fn sanitize_token(token: String) -> Result<String, &'static str> {
+///     if token.len() == 8 && token.chars().all(|c| c.is_ascii_hexdigit()) {
+///         Ok(token)
+///     } else {
+///         Err("invalid token")
+///     }
+/// }
+///
+/// let token: TokenUntrusted = Token("deadbeef".into()).to_untrusted_variant();
+/// assert_eq!(token.sanitize(sanitize_token), Ok(Token("deadbeef".into())));
+///
+/// let token: TokenUntrusted = Token("<script>".into()).into();
+/// assert_eq!(token.sanitize(sanitize_token), Err("invalid token"));
+/// ```
+///
+/// Deriving `UntrustedNewtype` for a struct with named fields or more than one field
+/// is a compile error:
+/// ```compile_fail
+/// # use untrusted_value::derive::UntrustedNewtype;
+/// #[derive(UntrustedNewtype)]
+/// pub struct Credentials(String, String);
+/// ```
+///
+/// # Panics
+/// This macro will panic if the annotated struct is not valid Rust code.
+#[proc_macro_derive(UntrustedNewtype)]
+pub fn untrusted_newtype_derive(input: TokenStream) -> TokenStream {
+    let ast = syn::parse(input).unwrap();
+    untrusted_newtype::impl_untrusted_newtype_macro(&ast).into()
+}
+
 /// This macro can be used to annotate structs and automatically implement
 /// the `untrusted_value::SanitizeValue` trait.
 ///
 /// The implementation implements the `SanitizeValue` trait on `UntrustedValue<AnnotatedType>`.
 /// Struct member types are required to implement the `SanitizeValue(MemberType)` trait.
+/// Members that are already of type `UntrustedValue<_>` are not sanitized, they stay
+/// tainted in the sanitized struct.
+///
+/// ```rust
+/// use untrusted_value::derive::SanitizeValue;
+/// use untrusted_value::{SanitizeValue, SanitizeWith, UntrustedValue};
+///
+/// struct Port(u16);
+///
+/// impl SanitizeValue<Port> for Port {
+///     type Error = ();
+///
+///     fn sanitize_value(self) -> Result<Port, Self::Error> {
+///         if self.0 >= 1024 { Ok(self) } else { Err(()) }
+///     }
+/// }
+///
+/// #[derive(SanitizeValue)]
+/// struct Request {
+///     port: Port,
+///     comment: UntrustedValue<String>,
+/// }
+///
+/// let request = UntrustedValue::from(Request {
+///     port: Port(8080),
+///     comment: UntrustedValue::from("Hello".to_string()),
+/// });
+///
+/// let request: Request = request.sanitize_value().expect("Sanitization failed");
+/// assert_eq!(request.port.0, 8080);
+///
+/// // The comment is still tainted
+/// let comment = request.comment.sanitize_with(|comment| Ok::<String, ()>(comment));
+/// assert_eq!(comment, Ok("Hello".to_string()));
+/// ```
+///
+/// The derive also implements `SanitizeValue<Box<AnnotatedType>>` on `UntrustedValue<Box<AnnotatedType>>`:
+/// ```rust
+/// # use untrusted_value::derive::SanitizeValue;
+/// # use untrusted_value::{SanitizeValue, UntrustedValue};
+/// #
+/// # struct Port(u16);
+/// #
+/// # impl SanitizeValue<Port> for Port {
+/// #     type Error = ();
+/// #
+/// #     fn sanitize_value(self) -> Result<Port, Self::Error> {
+/// #         if self.0 >= 1024 { Ok(self) } else { Err(()) }
+/// #     }
+/// # }
+/// #
+/// #[derive(SanitizeValue)]
+/// struct Request {
+///     port: Port,
+/// }
+///
+/// let request = UntrustedValue::from(Box::new(Request { port: Port(8080) }));
+/// let request: Box<Request> = request.sanitize_value().expect("Sanitization failed");
+/// assert_eq!(request.port.0, 8080);
+/// ```
+///
+/// Members whose type can not implement `SanitizeValue`, like borrowed `&'a str`, may be
+/// sanitized using a sanitizer function `#[sanitize_with(sanitizer)]` with the signature
+/// `fn(MemberType) -> Result<MemberType, E>`, where `E: Into<ErrorType>`. In this case, the
+/// error type of the implementation must be given by `#[sanitize_value(error = ErrorType)]`
+/// on the struct.
+///
+/// ```rust
+/// use untrusted_value::derive::SanitizeValue;
+/// use untrusted_value::{SanitizeValue, UntrustedValue};
+///
+/// #[derive(Debug, PartialEq)]
+/// struct InvalidName;
+///
+/// fn sanitize_name(name: &str) -> Result<&str, InvalidName> {
+///     if name.chars().all(char::is_alphanumeric) { Ok(name) } else { Err(InvalidName) }
+/// }
+///
+/// #[derive(SanitizeValue)]
+/// #[sanitize_value(error = InvalidName)]
+/// struct View<'a> {
+///     #[sanitize_with(sanitize_name)]
+///     name: &'a str,
+/// }
+///
+/// let view = UntrustedValue::from(View { name: "alice" });
+/// let view: View = view.sanitize_value().expect("Sanitization failed");
+/// assert_eq!(view.name, "alice");
+///
+/// let view = UntrustedValue::from(View { name: "<script>" });
+/// let view: Result<View, _> = view.sanitize_value();
+/// assert_eq!(view.err(), Some(InvalidName));
+/// ```
 ///
 /// When using the `derive_harden_sanitize` feature first all sanitizer functions
 /// are called. Then the (first) error (if any) is propagated.
@@ -163,11 +581,157 @@ pub fn untrusted_variant_derive(input: TokenStream) -> TokenStream {
 /// error is propagated directly.
 ///
 /// # Panics
-/// This macro will panic if the annotated struct is not valid Rust code.
-#[proc_macro_derive(SanitizeValue)]
+/// This macro will panic if the annotated struct is not valid Rust code,
+/// or if `#[sanitize_with(...)]` is used without an error type.
+#[proc_macro_derive(SanitizeValue, attributes(sanitize_value, sanitize_with))]
 pub fn sanitize_value_derive(input: TokenStream) -> TokenStream {
     let ast = syn::parse(input).unwrap();
-    sanitize_value::impl_sanitize_value_macro(&ast).into()
+    sanitize_value::impl_sanitize_value_macro(&ast, false).into()
+}
+
+/// This macro can be used to annotate structs and automatically implement
+/// the `untrusted_value::SanitizeValueWithReport` trait, like the `SanitizeValue` macro does
+/// for the `SanitizeValue` trait.
+///
+/// The implementation is generic over the report type. Struct member types are required to
+/// implement `SanitizeValueWithReport(MemberType, Report)`, the report is passed to
+/// each member's sanitization. Members with `#[sanitize_with(...)]` are sanitized without report.
+///
+/// ```rust
+/// use untrusted_value::derive::SanitizeValueWithReport;
+/// use untrusted_value::{SanitizeValueWithReport, UntrustedValue};
+///
+/// #[derive(Default)]
+/// struct Report {
+///     modified: Vec<&'static str>,
+/// }
+///
+/// struct Name(String);
+///
+/// impl SanitizeValueWithReport<Name, Report> for Name {
+///     type Error = ();
+///
+///     fn sanitize_value_with_report(self, report: &mut Report) -> Result<Name, Self::Error> {
+///         let trimmed = self.0.trim();
+///         if trimmed.len() != self.0.len() {
+///             report.modified.push("name");
+///         }
+///         Ok(Name(trimmed.to_string()))
+///     }
+/// }
+///
+/// struct Age(u8);
+///
+/// impl SanitizeValueWithReport<Age, Report> for Age {
+///     type Error = ();
+///
+///     fn sanitize_value_with_report(self, report: &mut Report) -> Result<Age, Self::Error> {
+///         if self.0 > 150 {
+///             report.modified.push("age");
+///             return Ok(Age(150));
+///         }
+///         Ok(self)
+///     }
+/// }
+///
+/// #[derive(SanitizeValueWithReport)]
+/// struct Person {
+///     name: Name,
+///     age: Age,
+/// }
+///
+/// let person = UntrustedValue::from(Person { name: Name(" alice ".into()), age: Age(30) });
+///
+/// let mut report = Report::default();
+/// let person: Person = person
+///     .sanitize_value_with_report(&mut report)
+///     .expect("Sanitization failed");
+///
+/// assert_eq!(person.name.0, "alice");
+/// assert_eq!(report.modified, vec!["name"]);
+/// ```
+///
+/// # Panics
+/// This macro will panic if the annotated struct is not valid Rust code,
+/// or if `#[sanitize_with(...)]` is used without an error type.
+#[proc_macro_derive(SanitizeValueWithReport, attributes(sanitize_value, sanitize_with))]
+pub fn sanitize_value_with_report_derive(input: TokenStream) -> TokenStream {
+    let ast = syn::parse(input).unwrap();
+    sanitize_value::impl_sanitize_value_macro(&ast, true).into()
+}
+
+/// This macro can be used to annotate structs that only need validation, i.e. the
+/// sanitized value is the same as the untrusted value, and automatically implement
+/// the `untrusted_value::SanitizeValue` trait on `UntrustedValue<AnnotatedType>`.
+///
+/// Each field requires a validator `#[validate_with(validator)]`, a function with the signature
+/// `fn(&FieldType) -> Result<(), E>`, where `E: Into<ErrorType>`. The error type of the
+/// implementation is given by `#[validate_with(error = ErrorType)]` on the struct.
+/// If all validators succeed, the value is returned unchanged.
+///
+/// ```rust
+/// use untrusted_value::derive::ValidateInPlace;
+/// use untrusted_value::{SanitizeValue, UntrustedValue};
+///
+/// #[derive(Debug, PartialEq)]
+/// enum ConfigError {
+///     PrivilegedPort,
+///     EmptyHost,
+/// }
+///
+/// fn unprivileged(port: &u16) -> Result<(), ConfigError> {
+///     if *port >= 1024 { Ok(()) } else { Err(ConfigError::PrivilegedPort) }
+/// }
+///
+/// fn non_empty(host: &String) -> Result<(), ConfigError> {
+///     if host.is_empty() { Err(ConfigError::EmptyHost) } else { Ok(()) }
+/// }
+///
+/// #[derive(ValidateInPlace)]
+/// #[validate_with(error = ConfigError)]
+/// struct Config {
+///     #[validate_with(unprivileged)]
+///     port: u16,
+///     #[validate_with(non_empty)]
+///     host: String,
+/// }
+///
+/// let config = UntrustedValue::from(Config { port: 8080, host: "localhost".to_string() });
+/// let config: Config = config.sanitize_value().expect("Validation failed");
+/// assert_eq!(config.port, 8080);
+///
+/// let config = UntrustedValue::from(Config { port: 80, host: "localhost".to_string() });
+/// let config: Result<Config, _> = config.sanitize_value();
+/// assert_eq!(config.err(), Some(ConfigError::PrivilegedPort));
+/// ```
+///
+/// When using the `derive_harden_sanitize` feature first all validators
+/// are called. Then the (first) error (if any) is propagated.
+///
+/// A field without validator is a compile error:
+/// ```compile_fail
+/// # use untrusted_value::derive::ValidateInPlace;
+/// #
+/// # fn unprivileged(port: &u16) -> Result<(), ()> {
+/// #     if *port >= 1024 { Ok(()) } else { Err(()) }
+/// # }
+/// #
+/// #[derive(ValidateInPlace)]
+/// #[validate_with(error = ())]
+/// struct Config {
+///     #[validate_with(unprivileged)]
+///     port: u16,
+///     host: String, // error: each field requires a validator
+/// }
+/// ```
+///
+/// # Panics
+/// This macro will panic if the annotated struct is not valid Rust code
+/// or the error type is missing.
+#[proc_macro_derive(ValidateInPlace, attributes(validate_with))]
+pub fn validate_in_place_derive(input: TokenStream) -> TokenStream {
+    let ast = syn::parse(input).unwrap();
+    validate_in_place::impl_validate_in_place_macro(&ast).into()
 }
 
 /// This macro can be used to annotate functions to automatically wrap the
@@ -202,9 +766,105 @@ pub fn sanitize_value_derive(input: TokenStream) -> TokenStream {
 ///
 /// Note that: This macro will generate a compile error if a function argument is marked
 /// as mutable. Since an `UntrustedValue` can not be mutable.
+///
+/// The function signature, including generics, lifetimes and where clauses, is kept as is.
+/// Arguments of a generic type `T` are wrapped as `UntrustedValue<T>`:
+/// ```rust
+/// # use untrusted_value::derive::untrusted_inputs;
+/// use std::str::FromStr;
+/// use untrusted_value::{SanitizeWith, UntrustedValue};
+///
+/// #[untrusted_inputs]
+/// fn parse<'a, T>(body: &'a str, default: T) -> T
+/// where
+///     T: FromStr,
+/// {
+///     let default: UntrustedValue<T> = default;
+///     let body: UntrustedValue<&'a str> = body;
+///     body.sanitize_with(str::parse)
+///         .unwrap_or_else(|_| default.use_untrusted_value())
+/// }
+///
+/// assert_eq!(parse("42", 0u32), 42);
+/// assert_eq!(parse("abc", 0u32), 0);
+/// ```
+///
+/// Attributes of the function arguments are kept. Arguments that are conditionally compiled
+/// using `#[cfg(...)]` are only wrapped if they are present:
+/// ```rust
+/// # use untrusted_value::derive::untrusted_inputs;
+/// use untrusted_value::UntrustedValue;
+///
+/// #[untrusted_inputs]
+/// fn index(
+///     name: &str,
+///     #[cfg(feature = "x")] trace_id: u64,
+///     #[cfg(not(feature = "x"))] session: &str,
+/// ) -> usize {
+///     let name: UntrustedValue<&str> = name;
+///     #[cfg(not(feature = "x"))]
+///     let session: UntrustedValue<&str> = session;
+///     # let _ = session;
+///     name.use_untrusted_value().len()
+/// }
+///
+/// assert_eq!(index("alice", "session"), 5);
+/// ```
+///
+/// Using `#[untrusted_inputs(maybe)]` the function arguments are wrapped as
+/// `MaybeUntrusted::Untrusted` instead. This is useful when other code paths provide
+/// trusted values of the same `MaybeUntrusted<ArgType>` type, such that all values
+/// can be handled identically:
+/// ```rust
+/// # use untrusted_value::derive::untrusted_inputs;
+/// use untrusted_value::{MaybeUntrusted, SanitizeWith};
+///
+/// fn sanitize_name(name: &str) -> Result<&str, ()> {
+///     if name.chars().all(char::is_alphanumeric) {
+///         Ok(name)
+///     } else {
+///         Err(())
+///     }
+/// }
+///
+/// #[untrusted_inputs(maybe)]
+/// fn index(name: &str) -> Result<String, ()> {
+///     let name: MaybeUntrusted<&str> = name;
+///     assert!(name.is_untrusted());
+///
+///     let name = name.sanitize_with(sanitize_name)?;
+///     Ok(format!("Hello, {}!", name))
+/// }
+///
+/// assert_eq!(index("world"), Ok("Hello, world!".to_string()));
+/// assert!(index("<script>").is_err());
+/// ```
+///
+/// Arguments of an `impl Trait` type are supported as well:
+/// ```rust
+/// # use untrusted_value::derive::untrusted_inputs;
+/// use untrusted_value::SanitizeWith;
+///
+/// #[untrusted_inputs(maybe)]
+/// fn greet(name: impl AsRef<str>) -> Result<String, ()> {
+///     assert!(name.is_untrusted());
+///
+///     let name = name.sanitize_with(|name| {
+///         if name.as_ref().chars().all(char::is_alphanumeric) {
+///             Ok(name)
+///         } else {
+///             Err(())
+///         }
+///     })?;
+///     Ok(format!("Hello, {}!", name.as_ref()))
+/// }
+///
+/// assert_eq!(greet("world"), Ok("Hello, world!".to_string()));
+/// assert_eq!(greet(String::from("<script>")), Err(()));
+/// ```
 #[proc_macro_attribute]
-pub fn untrusted_inputs(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    untrusted_inputs::impl_untrusted_inputs_macro(item.into()).into()
+pub fn untrusted_inputs(attr: TokenStream, item: TokenStream) -> TokenStream {
+    untrusted_inputs::impl_untrusted_inputs_macro(attr.into(), item.into()).into()
 }
 
 /// This macro can be used to annotate functions to automatically wrap the
@@ -247,9 +907,136 @@ pub fn untrusted_inputs(_attr: TokenStream, item: TokenStream) -> TokenStream {
 /// such that a normal library user can use the function without caring about tainted data.
 /// When enabling the feature `some_feature` the function output is wrapped in `UntrustedValue`
 /// and marked as tainted.
+///
+/// An opaque `impl Trait` return type can not be wrapped. For functions returning
+/// `impl Iterator<Item = T>`, each item can be wrapped instead using `#[untrusted_output(wrap_each)]`,
+/// such that the function returns `impl Iterator<Item = UntrustedValue<T>>`:
+/// ```rust
+/// # use untrusted_value::derive::untrusted_output;
+/// # use untrusted_value::UntrustedValue;
+/// #
+/// #[untrusted_output(wrap_each)]
+/// fn lines(input: &str) -> impl Iterator<Item = String> + '_ {
+///     input.lines().map(ToString::to_string)
+/// }
+///
+/// let lines: Vec<UntrustedValue<String>> = lines("a\nb").collect();
+/// assert_eq!(lines.len(), 2);
+/// ```
+///
+/// ```compile_fail
+/// # use untrusted_value::derive::untrusted_output;
+/// #
+/// #[untrusted_output] // error: can not wrap an `impl Trait` return type
+/// fn lines(input: &str) -> impl Iterator<Item = String> + '_ {
+///     input.lines().map(ToString::to_string)
+/// }
+/// ```
+///
+/// Due to the orphan rules, `SanitizeValue<ForeignType>` can not be implemented for
+/// `UntrustedValue<ForeignType>` outside of this crate.
+/// Using `#[untrusted_output(newtype = Name)]`, the output is wrapped in a newtype
+/// `Name(UntrustedValue<ReturnType>)` declared next to the function, such that
+/// the output has its own type that the library can implement sanitization traits for:
+/// ```rust
+/// # use untrusted_value::derive::untrusted_output;
+/// # use untrusted_value::{SanitizeValue, SanitizeWith, UntrustedValue};
+/// #
+/// #[untrusted_output(newtype = UntrustedHostname)]
+/// pub fn hostname() -> String {
+///     "localhost".to_string()
+/// }
+///
+/// impl SanitizeValue<String> for UntrustedHostname {
+///     type Error = ();
+///
+///     fn sanitize_value(self) -> Result<String, Self::Error> {
+///         self.0.sanitize_with(|hostname| {
+///             if hostname.chars().all(|c| c.is_ascii_alphanumeric() || c == '.') {
+///                 Ok(hostname)
+///             } else {
+///                 Err(())
+///             }
+///         })
+///     }
+/// }
+///
+/// let hostname: UntrustedHostname = hostname();
+/// assert_eq!(hostname.sanitize_value(), Ok("localhost".to_string()));
+/// ```
+///
+/// The newtype converts from and into `UntrustedValue<ReturnType>`. This way, downstream crates may
+/// use their own sanitizers on the output, or reuse the library's sanitization for untrusted values
+/// obtained from other sources:
+/// ```rust
+/// # use untrusted_value::derive::untrusted_output;
+/// # use untrusted_value::{SanitizeValue, SanitizeWith, UntrustedValue};
+/// #
+/// # #[untrusted_output(newtype = UntrustedHostname)]
+/// # pub fn hostname() -> String {
+/// #     "localhost".to_string()
+/// # }
+/// #
+/// # impl SanitizeValue<String> for UntrustedHostname {
+/// #     type Error = ();
+/// #
+/// #     fn sanitize_value(self) -> Result<String, Self::Error> {
+/// #         self.0.sanitize_with(|hostname| {
+/// #             if hostname.chars().all(|c| c.is_ascii_alphanumeric() || c == '.') {
+/// #                 Ok(hostname)
+/// #             } else {
+/// #                 Err(())
+/// #             }
+/// #         })
+/// #     }
+/// # }
+/// #
+/// // downstream: sanitize the output using an own sanitizer
+/// let hostname: UntrustedValue<String> = hostname().into();
+/// let length = hostname.sanitize_with(|hostname| Ok::<usize, ()>(hostname.len()));
+/// assert_eq!(length, Ok(9));
+///
+/// // downstream: sanitize a value from another source using the library's sanitizer
+/// let hostname = UntrustedValue::from("<script>".to_string());
+/// let hostname = UntrustedHostname::from(hostname).sanitize_value();
+/// assert_eq!(hostname, Err(()));
+/// ```
+///
+/// The newtype is not generic, hence generic functions and return types borrowing data are rejected:
+/// ```compile_fail
+/// # use untrusted_value::derive::untrusted_output;
+/// #
+/// #[untrusted_output(newtype = UntrustedLine)] // error: can not wrap a return type containing references
+/// pub fn first_line(input: &str) -> &str {
+///     input.lines().next().unwrap_or_default()
+/// }
+/// ```
+/// ```compile_fail
+/// # use untrusted_value::derive::untrusted_output;
+/// #
+/// #[untrusted_output(newtype = UntrustedValueOf)] // error: can not be used on generic functions
+/// pub fn parse<T: std::str::FromStr>(input: String) -> Option<T> {
+///     input.parse().ok()
+/// }
+/// ```
+///
+/// Since the newtype is declared next to the function, the function must be a free function.
+/// Methods are rejected:
+/// ```compile_fail
+/// # use untrusted_value::derive::untrusted_output;
+/// #
+/// struct Config;
+///
+/// impl Config {
+///     #[untrusted_output(newtype = UntrustedHostname)] // error: not a free function
+///     pub fn hostname(&self) -> String {
+///         "localhost".to_string()
+///     }
+/// }
+/// ```
 #[proc_macro_attribute]
-pub fn untrusted_output(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    untrusted_output::impl_untrusted_output_macro(item.into()).into()
+pub fn untrusted_output(attr: TokenStream, item: TokenStream) -> TokenStream {
+    untrusted_output::impl_untrusted_output_macro(attr.into(), item.into()).into()
 }
 
 /// This macro can be used to annotate modules/functions/blocks.
@@ -329,6 +1116,10 @@ mod sanitize_with;
 #[allow(clippy::module_name_repetitions)]
 mod untrusted_inputs;
 #[allow(clippy::module_name_repetitions)]
+mod untrusted_newtype;
+#[allow(clippy::module_name_repetitions)]
 mod untrusted_output;
 #[allow(clippy::module_name_repetitions)]
 mod untrusted_variant;
+#[allow(clippy::module_name_repetitions)]
+mod validate_in_place;
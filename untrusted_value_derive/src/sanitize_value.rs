@@ -1,13 +1,69 @@
 use crate::extract_struct_fields_from_ast;
-use proc_macro2::{Ident, TokenStream};
+use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
-use syn::{parse_quote, ImplGenerics, Type};
+use syn::parse::Parse;
+use syn::{parse2, parse_quote, Attribute, Generics, Meta, Path, PathArguments, Token, Type};
+
+/// Parameters of the `#[sanitize_value(error = ErrorType)]` attribute on the struct
+struct Parameters {
+    error: Type,
+}
+
+impl Parse for Parameters {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let ident: Ident = input.parse()?;
+        if ident != "error" {
+            return Err(syn::Error::new(
+                ident.span(),
+                "Unknown parameter, expected `error`",
+            ));
+        }
+        input.parse::<Token![=]>()?;
+        let error: Type = input.parse()?;
+
+        // Consume an optional comma
+        let _ = input.parse::<Token![,]>().ok();
+
+        Ok(Parameters { error })
+    }
+}
+
+/// Returns the tokens of the attribute with the given name, if present
+fn attribute_tokens(attrs: &[Attribute], name: &str) -> Option<TokenStream> {
+    attrs
+        .iter()
+        .find(|a| a.path().is_ident(name))
+        .map(|attribute| match attribute.meta {
+            Meta::List(ref meta) => meta.tokens.clone(),
+            _ => panic!("Expected a list of parameters within #[{name}(...)]"),
+        })
+}
+
+/// Returns the error type given by `#[sanitize_value(error = ErrorType)]` on the struct, if present
+pub fn struct_error(ast: &syn::DeriveInput) -> Option<Type> {
+    attribute_tokens(&ast.attrs, "sanitize_value").map(|tokens| {
+        parse2::<Parameters>(tokens)
+            .expect("Expected #[sanitize_value(error = ErrorType)] on the struct")
+            .error
+    })
+}
+
+/// Returns the sanitizer function given by `#[sanitize_with(sanitizer)]` on the field, if present
+pub fn field_sanitizer(field: &syn::Field) -> Option<Path> {
+    attribute_tokens(&field.attrs, "sanitize_with").map(|tokens| {
+        parse2::<Path>(tokens).expect("Expected a sanitizer function within #[sanitize_with(...)]")
+    })
+}
 
 #[derive(Clone)]
 pub struct FieldInfo<'a> {
     pub name: &'a Option<Ident>,
     pub field_type: Type,
     pub field_target_type: Type,
+    /// Whether the field is sanitized, otherwise it is moved to the target as is
+    pub sanitize: bool,
+    /// Sanitizer function used instead of the `SanitizeValue` implementation of the field type
+    pub sanitizer: Option<Path>,
 }
 
 #[derive(Clone)]
@@ -15,9 +71,14 @@ pub struct SanitizeValueMacroCustomParameters<'a> {
     pub struct_type: &'a Type,
     pub struct_type_target: &'a Type,
     pub fields: Vec<FieldInfo<'a>>,
+    /// Whether the struct type is wrapped in an `UntrustedValue` that must be unwrapped first
+    pub unwrap_source: bool,
+    /// Error type of the implementation, generic if not given
+    pub error: Option<Type>,
+    /// Whether to implement `SanitizeValueWithReport` instead of `SanitizeValue`
+    pub with_report: bool,
 
-    pub impl_generics: ImplGenerics<'a>,
-    pub where_clause: Option<&'a syn::WhereClause>,
+    pub generics: &'a Generics,
 }
 
 pub fn impl_sanitize_value_custom(params: SanitizeValueMacroCustomParameters) -> TokenStream {
@@ -25,96 +86,200 @@ pub fn impl_sanitize_value_custom(params: SanitizeValueMacroCustomParameters) ->
         struct_type,
         struct_type_target,
         fields,
-        impl_generics,
-        where_clause,
+        unwrap_source,
+        error,
+        with_report,
+        generics,
     } = params;
 
-    let where_fields = fields.iter().map(|f| {
-        let field_type = &f.field_type;
-        let new_field_type = &f.field_target_type;
-        quote! {
-            #field_type: ::untrusted_value::SanitizeValue<#new_field_type, Error = CommonSanitizationError>,
-        }
+    let mut generics = generics.clone();
+    if with_report {
+        generics.params.push(parse_quote!(Report: ?Sized));
+    }
+    let error: Type = error.unwrap_or_else(|| {
+        generics.params.push(parse_quote!(CommonSanitizationError));
+        parse_quote!(CommonSanitizationError)
     });
 
-    let where_clause = if where_clause.is_none() {
+    let where_fields = fields
+        .iter()
+        .filter(|f| f.sanitize && f.sanitizer.is_none())
+        .map(|f| -> syn::WherePredicate {
+            let field_type = &f.field_type;
+            let new_field_type = &f.field_target_type;
+            if with_report {
+                parse_quote! {
+                    #field_type: ::untrusted_value::SanitizeValueWithReport<#new_field_type, Report, Error = #error>
+                }
+            } else {
+                parse_quote! {
+                    #field_type: ::untrusted_value::SanitizeValue<#new_field_type, Error = #error>
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+    generics.make_where_clause().predicates.extend(where_fields);
+
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    let unwrap_source = if unwrap_source {
         quote! {
-            where #(#where_fields)*
+            let source = ::untrusted_value::UntrustedValue::use_untrusted_value(self);
         }
     } else {
-        let where_clause = where_clause.unwrap();
         quote! {
-            #where_clause #(#where_fields)*
+            let source = self;
         }
     };
 
-    let impl_generics = quote! {
-        <#impl_generics CommonSanitizationError>
-    };
+    let create_struct = create_sanitized_struct(
+        &struct_expression_path(struct_type_target),
+        &fields,
+        &error,
+        with_report,
+    );
 
-    let create_struct = {
-        #[cfg(not(feature = "harden_sanitize"))]
-        {
-            let mutate_fields = fields.iter().map(|f| {
-                let field_name = f.name;
-                quote! {
-                    #field_name: self.#field_name.sanitize_value()?,
+    if with_report {
+        quote! {
+            // STRUCT -> sanitize_value_with_report -> TARGET
+            #[automatically_derived]
+            impl #impl_generics ::untrusted_value::SanitizeValueWithReport<#struct_type_target, Report> for #struct_type #where_clause {
+                type Error = #error;
+                fn sanitize_value_with_report(self, report: &mut Report) -> Result<#struct_type_target, Self::Error> {
+                    #unwrap_source
+                    #create_struct
                 }
-            });
+            }
+        }
+    } else {
+        quote! {
+            // STRUCT -> sanitize_value -> TARGET
+            #[automatically_derived]
+            impl #impl_generics ::untrusted_value::SanitizeValue<#struct_type_target> for #struct_type #where_clause {
+                type Error = #error;
+                fn sanitize_value(self) -> Result<#struct_type_target, Self::Error> {
+                    #unwrap_source
+                    #create_struct
+                }
+            }
+        }
+    }
+}
 
+/// Sanitizes a field of `source`, evaluates to a `Result` of the sanitized field
+fn sanitize_field(field: &FieldInfo, error: &Type, with_report: bool) -> TokenStream {
+    let field_name = field.name;
+    if let Some(sanitizer) = &field.sanitizer {
+        if is_untrusted_value_type(&field.field_type)
+            && !is_untrusted_value_type(&field.field_target_type)
+        {
+            // field of an untrusted variant, the sanitizer is called with the tainted value
+            quote! {
+                ::untrusted_value::SanitizeWith::sanitize_with(source.#field_name, #sanitizer)
+                    .map_err(::core::convert::Into::<#error>::into)
+            }
+        } else {
             quote! {
-                Ok(#struct_type_target {
-                    #(#mutate_fields)*
-                })
+                #sanitizer(source.#field_name).map_err(::core::convert::Into::<#error>::into)
             }
         }
-        #[cfg(feature = "harden_sanitize")]
-        {
-            let mutate_fields = fields.iter().map(|f| {
-                let field_name = f.name;
-                quote! {
-                    let #field_name = self.#field_name.sanitize_value();
-                }
-            });
+    } else if with_report {
+        quote! {
+            ::untrusted_value::SanitizeValueWithReport::sanitize_value_with_report(source.#field_name, report)
+        }
+    } else {
+        quote! {
+            ::untrusted_value::SanitizeValue::sanitize_value(source.#field_name)
+        }
+    }
+}
+
+/// Struct expressions require generic arguments in turbofish notation, e.g. `View::<'a> { .. }`
+fn struct_expression_path(struct_type: &Type) -> Type {
+    let mut struct_type = struct_type.clone();
+    if let Type::Path(type_path) = &mut struct_type {
+        for segment in &mut type_path.path.segments {
+            if let PathArguments::AngleBracketed(arguments) = &mut segment.arguments {
+                arguments.colon2_token = Some(Token![::](Span::call_site()));
+            }
+        }
+    }
+    struct_type
+}
 
-            let error = fields.iter().map(|f| {
-                let field_name = f.name;
+/// Creates the sanitized target struct from the fields of `source`
+fn create_sanitized_struct(
+    struct_type_target: &Type,
+    fields: &[FieldInfo],
+    error: &Type,
+    with_report: bool,
+) -> TokenStream {
+    #[cfg(not(feature = "harden_sanitize"))]
+    {
+        let mutate_fields = fields.iter().map(|f| {
+            let field_name = f.name;
+            if f.sanitize {
+                let sanitized = sanitize_field(f, error, with_report);
                 quote! {
-                    let #field_name = #field_name?;
+                    #field_name: #sanitized?,
                 }
-            });
-
-            let struct_fields = fields.iter().map(|f| {
-                let field_name = f.name;
+            } else {
                 quote! {
-                    #field_name,
+                    #field_name: source.#field_name,
                 }
-            });
+            }
+        });
 
-            quote! {
+        quote! {
+            Ok(#struct_type_target {
                 #(#mutate_fields)*
-                #(#error)*
+            })
+        }
+    }
+    #[cfg(feature = "harden_sanitize")]
+    {
+        let mutate_fields = fields.iter().filter(|f| f.sanitize).map(|f| {
+            let field_name = f.name;
+            let sanitized = sanitize_field(f, error, with_report);
+            quote! {
+                let #field_name = #sanitized;
+            }
+        });
 
-                Ok(#struct_type_target {
-                    #(#struct_fields)*
-                })
+        let moved_fields = fields.iter().filter(|f| !f.sanitize).map(|f| {
+            let field_name = f.name;
+            quote! {
+                let #field_name = source.#field_name;
             }
-        }
-    };
+        });
 
-    quote! {
-        // STRUCT -> sanitize_value -> TARGET
-        #[automatically_derived]
-        impl #impl_generics ::untrusted_value::SanitizeValue<#struct_type_target> for #struct_type #where_clause {
-            type Error = CommonSanitizationError;
-            fn sanitize_value(self) -> Result<#struct_type_target, Self::Error> {
-                #create_struct
+        let error = fields.iter().filter(|f| f.sanitize).map(|f| {
+            let field_name = f.name;
+            quote! {
+                let #field_name = #field_name?;
             }
+        });
+
+        let struct_fields = fields.iter().map(|f| {
+            let field_name = f.name;
+            quote! {
+                #field_name,
+            }
+        });
+
+        quote! {
+            #(#mutate_fields)*
+            #(#moved_fields)*
+            #(#error)*
+
+            Ok(#struct_type_target {
+                #(#struct_fields)*
+            })
         }
     }
 }
 
-pub fn impl_sanitize_value_macro(ast: &syn::DeriveInput) -> TokenStream {
+pub fn impl_sanitize_value_macro(ast: &syn::DeriveInput, with_report: bool) -> TokenStream {
     let name = &ast.ident;
 
     let modified_fields: Vec<FieldInfo> = extract_struct_fields_from_ast(ast)
@@ -122,15 +287,24 @@ pub fn impl_sanitize_value_macro(ast: &syn::DeriveInput) -> TokenStream {
         .map(|f| {
             let field_name = &f.ident;
             let field_type = &f.ty;
+            let sanitizer = field_sanitizer(f);
             FieldInfo {
                 name: field_name,
                 field_target_type: field_type.clone(),
                 field_type: field_type.clone(),
+                sanitize: sanitizer.is_some() || !is_untrusted_value_type(field_type),
+                sanitizer,
             }
         })
         .collect();
 
-    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    let error = struct_error(ast);
+    assert!(
+        error.is_some() || modified_fields.iter().all(|f| f.sanitizer.is_none()),
+        "Fields with #[sanitize_with(...)] require #[sanitize_value(error = ErrorType)] on the struct"
+    );
+
+    let (_, ty_generics, _) = ast.generics.split_for_impl();
 
     let name_wrap = parse_quote!(::untrusted_value::UntrustedValue<#name #ty_generics>);
     let name_source = &name_wrap;
@@ -142,9 +316,61 @@ pub fn impl_sanitize_value_macro(ast: &syn::DeriveInput) -> TokenStream {
         struct_type: name_source,
         struct_type_target: name_target,
         fields: modified_fields,
-        impl_generics,
-        where_clause,
+        unwrap_source: true,
+        error,
+        with_report,
+        generics: &ast.generics,
     };
 
-    impl_sanitize_value_custom(parameters)
+    let derive = impl_sanitize_value_custom(parameters);
+    let boxed = if with_report {
+        quote! {}
+    } else {
+        impl_sanitize_value_boxed(&ast.ident, &ast.generics)
+    };
+
+    quote! {
+        #derive
+        #boxed
+    }
+}
+
+/// Implements `SanitizeValue<Box<STRUCT>>` for `UntrustedValue<Box<STRUCT>>`, using the
+/// `SanitizeValue<STRUCT>` implementation of `UntrustedValue<STRUCT>`. This allows sanitizing
+/// boxed values, for example, of recursive types.
+///
+/// This can not be a blanket implementation in `untrusted_value`, since it would conflict with
+/// the implementation of `SanitizeValue` for all `UntrustedValue<T>`.
+pub fn impl_sanitize_value_boxed(name: &Ident, generics: &Generics) -> TokenStream {
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let mut generics = generics.clone();
+    generics.make_where_clause().predicates.push(parse_quote!(
+        ::untrusted_value::UntrustedValue<#name #ty_generics>: ::untrusted_value::SanitizeValue<#name #ty_generics>
+    ));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    quote! {
+        // UntrustedValue<Box<STRUCT>> -> sanitize_value -> Box<STRUCT>
+        #[automatically_derived]
+        impl #impl_generics ::untrusted_value::SanitizeValue<::std::boxed::Box<#name #ty_generics>> for ::untrusted_value::UntrustedValue<::std::boxed::Box<#name #ty_generics>> #where_clause {
+            type Error = <::untrusted_value::UntrustedValue<#name #ty_generics> as ::untrusted_value::SanitizeValue<#name #ty_generics>>::Error;
+
+            fn sanitize_value(self) -> Result<::std::boxed::Box<#name #ty_generics>, Self::Error> {
+                let value = ::untrusted_value::UntrustedValue::from(*self.use_untrusted_value());
+                ::untrusted_value::SanitizeValue::sanitize_value(value).map(::std::boxed::Box::new)
+            }
+        }
+    }
+}
+
+/// Fields already typed `UntrustedValue<_>` stay tainted in the sanitized struct.
+pub fn is_untrusted_value_type(field_type: &Type) -> bool {
+    match field_type {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "UntrustedValue"),
+        _ => false,
+    }
 }
@@ -1,9 +1,40 @@
 use proc_macro2::TokenStream;
 use quote::quote;
+use syn::parse::Parse;
 use syn::FnArg::{Receiver, Typed};
-use syn::{ItemFn, Pat};
+use syn::{Ident, ItemFn, Pat, Token};
 
-pub fn impl_untrusted_inputs_macro(item: TokenStream) -> TokenStream {
+#[derive(Default)]
+struct Parameters {
+    maybe: bool,
+}
+
+impl Parse for Parameters {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut parameters = Parameters::default();
+
+        while !input.is_empty() {
+            let ident: Ident = input.parse()?;
+            if ident == "maybe" {
+                parameters.maybe = true;
+            } else {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    "Unknown parameter, expected `maybe`",
+                ));
+            }
+
+            // Consume an optional comma
+            let _ = input.parse::<Token![,]>().ok();
+        }
+
+        Ok(parameters)
+    }
+}
+
+pub fn impl_untrusted_inputs_macro(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let parameters: Parameters =
+        syn::parse2(attr).expect("Expected a list of parameters within #[untrusted_inputs(...)]");
     let input_fn: ItemFn =
         syn::parse2(item).expect("This macro can only be used on function declaration");
 
@@ -34,10 +65,23 @@ pub fn impl_untrusted_inputs_macro(item: TokenStream) -> TokenStream {
                 );
             }
 
+            // Keep conditional compilation of the argument for its wrapping statement
+            let cfg_attrs = arg
+                .attrs
+                .iter()
+                .filter(|a| a.path().is_ident("cfg") || a.path().is_ident("cfg_attr"));
             let arg = &arg.pat;
 
-            quote! {
-                let #arg = ::untrusted_value::UntrustedValue::from(#arg);
+            if parameters.maybe {
+                quote! {
+                    #(#cfg_attrs)*
+                    let #arg = ::untrusted_value::MaybeUntrusted::<_>::wrap_untrusted(#arg);
+                }
+            } else {
+                quote! {
+                    #(#cfg_attrs)*
+                    let #arg = ::untrusted_value::UntrustedValue::from(#arg);
+                }
             }
         });
 
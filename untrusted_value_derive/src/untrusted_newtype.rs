@@ -0,0 +1,67 @@
+use crate::extract_struct_fields_from_ast;
+use crate::untrusted_variant::impl_into_untrusted_variant;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, Fields, Ident};
+
+pub fn impl_untrusted_newtype_macro(ast: &syn::DeriveInput) -> TokenStream {
+    let is_newtype = matches!(
+        &ast.data,
+        Data::Struct(data_struct) if matches!(&data_struct.fields, Fields::Unnamed(fields) if fields.unnamed.len() == 1)
+    );
+    if !is_newtype {
+        return syn::Error::new_spanned(
+            &ast.ident,
+            "UntrustedNewtype can only be derived for tuple structs with exactly one field",
+        )
+        .to_compile_error();
+    }
+
+    let name = &ast.ident;
+    let struct_visibility = &ast.vis;
+    let new_struct_name = Ident::new(&format!("{name}Untrusted"), name.span());
+
+    let field = &extract_struct_fields_from_ast(ast)[0];
+    let field_visibility = &field.vis;
+    let field_type = &field.ty;
+
+    let generics = &ast.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let into_untrusted_variant = impl_into_untrusted_variant(
+        name,
+        &new_struct_name,
+        generics,
+        &quote! { 0: ::untrusted_value::UntrustedValue::from(self.0) },
+        &quote! { 0: self.0.use_untrusted_value() },
+    );
+
+    let sanitize_with =
+        super::sanitize_with::impl_sanitize_with_custom(&new_struct_name, name, generics);
+
+    quote! {
+        #[automatically_derived]
+        #struct_visibility struct #new_struct_name #generics (#field_visibility ::untrusted_value::UntrustedValue<#field_type>) #where_clause;
+
+        #[automatically_derived]
+        impl #impl_generics #new_struct_name #ty_generics #where_clause {
+            /// Sanitizes the wrapped value using the provided sanitizer and
+            /// wraps the sanitized value in the newtype.
+            ///
+            /// # Errors
+            /// Returns the sanitizer's error if sanitization fails.
+            pub fn sanitize<Sanitizer, Error>(self, sanitizer: Sanitizer) -> Result<#name #ty_generics, Error>
+            where
+                Sanitizer: FnOnce(#field_type) -> Result<#field_type, Error>
+            {
+                ::untrusted_value::SanitizeWith::sanitize_with(self.0, sanitizer).map(#name)
+            }
+        }
+
+        // NEWTYPE <-> UNTRUSTED NEWTYPE <-> UntrustedValue<NEWTYPE>
+        #into_untrusted_variant
+
+        // UNTRUSTED NEWTYPE -> sanitize_with -> NEWTYPE
+        #sanitize_with
+    }
+}
@@ -1,8 +1,69 @@
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{parse_quote, ItemFn, ReturnType};
+use syn::parse::Parse;
+use syn::{
+    parse_quote, GenericArgument, Ident, ItemFn, PathArguments, ReturnType, Token, Type,
+    TypeImplTrait, TypeParamBound,
+};
 
-pub fn impl_untrusted_output_macro(item: TokenStream) -> TokenStream {
+#[derive(Default)]
+struct Parameters {
+    wrap_each: bool,
+    newtype: Option<Ident>,
+}
+
+impl Parse for Parameters {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut parameters = Parameters::default();
+
+        while !input.is_empty() {
+            let ident: Ident = input.parse()?;
+            if ident == "wrap_each" {
+                parameters.wrap_each = true;
+            } else if ident == "newtype" {
+                input.parse::<Token![=]>()?;
+                parameters.newtype = Some(input.parse()?);
+            } else {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    "Unknown parameter, expected `wrap_each` or `newtype`",
+                ));
+            }
+
+            // Consume an optional comma
+            let _ = input.parse::<Token![,]>().ok();
+        }
+
+        Ok(parameters)
+    }
+}
+
+/// Returns the `Item` type of an `impl Iterator<Item = T>` return type
+fn iterator_item_type(return_type: &mut TypeImplTrait) -> Option<&mut Type> {
+    return_type.bounds.iter_mut().find_map(|bound| {
+        let TypeParamBound::Trait(bound) = bound else {
+            return None;
+        };
+        let segment = bound.path.segments.last_mut()?;
+        if segment.ident != "Iterator" {
+            return None;
+        }
+        let PathArguments::AngleBracketed(arguments) = &mut segment.arguments else {
+            return None;
+        };
+        arguments
+            .args
+            .iter_mut()
+            .find_map(|argument| match argument {
+                GenericArgument::AssocType(assoc) if assoc.ident == "Item" => Some(&mut assoc.ty),
+                _ => None,
+            })
+    })
+}
+
+pub fn impl_untrusted_output_macro(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let parameters: Parameters =
+        syn::parse2(attr).expect("Expected a list of parameters within #[untrusted_output(...)]");
     let input_fn: ItemFn =
         syn::parse2(item).expect("This macro can only be used on function declaration");
 
@@ -13,17 +74,63 @@ pub fn impl_untrusted_output_macro(item: TokenStream) -> TokenStream {
         block,
     } = input_fn;
 
-    let output = match &sig.output {
+    let original_type = match &sig.output {
         ReturnType::Default => panic!(
             "Can not annotate function with #[untrusted_output] since it has no return value."
         ),
-        ReturnType::Type(_, type_box) => {
-            let original_type = type_box.as_ref();
-            parse_quote! { -> ::untrusted_value::UntrustedValue<#original_type> }
-        }
+        ReturnType::Type(_, type_box) => type_box.as_ref().clone(),
     };
 
-    sig.output = output;
+    if let Some(newtype) = parameters.newtype {
+        if parameters.wrap_each {
+            return syn::Error::new_spanned(
+                newtype,
+                "#[untrusted_output(newtype = ...)] can not be combined with `wrap_each`",
+            )
+            .to_compile_error();
+        }
+        return impl_untrusted_output_newtype(&newtype, &original_type, &attrs, &vis, sig, &block);
+    }
+
+    let body = match (original_type, parameters.wrap_each) {
+        (Type::ImplTrait(mut impl_trait), true) => {
+            let Some(item_type) = iterator_item_type(&mut impl_trait) else {
+                return syn::Error::new_spanned(
+                    impl_trait,
+                    "#[untrusted_output(wrap_each)] requires the return type `impl Iterator<Item = T>`",
+                )
+                .to_compile_error();
+            };
+            *item_type = parse_quote! { ::untrusted_value::UntrustedValue<#item_type> };
+            sig.output = parse_quote! { -> #impl_trait };
+
+            quote! {
+                ::core::iter::Iterator::map(#block, ::untrusted_value::UntrustedValue::wrap)
+            }
+        }
+        (original_type @ Type::ImplTrait(_), false) => {
+            return syn::Error::new_spanned(
+                original_type,
+                "#[untrusted_output] can not wrap an `impl Trait` return type, use a concrete return type \
+                 or #[untrusted_output(wrap_each)] to wrap each item of an `impl Iterator<Item = T>`",
+            )
+            .to_compile_error();
+        }
+        (original_type, true) => {
+            return syn::Error::new_spanned(
+                original_type,
+                "#[untrusted_output(wrap_each)] requires the return type `impl Iterator<Item = T>`",
+            )
+            .to_compile_error();
+        }
+        (original_type, false) => {
+            sig.output = parse_quote! { -> ::untrusted_value::UntrustedValue<#original_type> };
+
+            quote! {
+                ::untrusted_value::UntrustedValue::from(#block)
+            }
+        }
+    };
 
     // Split the function into its header and body
     let function_header = quote! {
@@ -32,7 +139,113 @@ pub fn impl_untrusted_output_macro(item: TokenStream) -> TokenStream {
 
     quote! {
         #function_header {
-            ::untrusted_value::UntrustedValue::from(#block)
+            #body
+        }
+    }
+}
+
+/// Wraps the function output in a newtype `#newtype(UntrustedValue<ReturnType>)`, declared
+/// next to the function with the same visibility.
+fn impl_untrusted_output_newtype(
+    newtype: &Ident,
+    original_type: &Type,
+    attrs: &[syn::Attribute],
+    vis: &syn::Visibility,
+    mut sig: syn::Signature,
+    block: &syn::Block,
+) -> TokenStream {
+    if let Type::ImplTrait(_) = original_type {
+        return syn::Error::new_spanned(
+            original_type,
+            "#[untrusted_output(newtype = ...)] can not wrap an `impl Trait` return type",
+        )
+        .to_compile_error();
+    }
+
+    // The newtype is declared next to the function, which is not possible inside an impl block.
+    // Associated functions can only be detected if they have a receiver or refer to `Self`.
+    if let Some(receiver) = sig.receiver() {
+        return syn::Error::new_spanned(
+            receiver,
+            "#[untrusted_output(newtype = ...)] can only be used on free functions, not on methods",
+        )
+        .to_compile_error();
+    }
+    if refers_to_self(&sig) {
+        return syn::Error::new_spanned(
+            &sig,
+            "#[untrusted_output(newtype = ...)] can only be used on free functions, not on associated functions",
+        )
+        .to_compile_error();
+    }
+
+    // The newtype is not generic, hence the return type can not depend on generics or lifetimes
+    if !sig.generics.params.is_empty() {
+        return syn::Error::new_spanned(
+            &sig.generics,
+            "#[untrusted_output(newtype = ...)] can not be used on generic functions",
+        )
+        .to_compile_error();
+    }
+    if borrows(original_type) {
+        return syn::Error::new_spanned(
+            original_type,
+            "#[untrusted_output(newtype = ...)] can not wrap a return type containing references or lifetimes, \
+             return an owned type instead",
+        )
+        .to_compile_error();
+    }
+
+    let newtype_doc = format!(
+        "Untrusted output of [`{}`], wrapping an `UntrustedValue`.",
+        sig.ident
+    );
+    sig.output = parse_quote! { -> #newtype };
+
+    quote! {
+        #[doc = #newtype_doc]
+        #vis struct #newtype(pub ::untrusted_value::UntrustedValue<#original_type>);
+
+        #[automatically_derived]
+        impl ::core::convert::From<::untrusted_value::UntrustedValue<#original_type>> for #newtype {
+            fn from(value: ::untrusted_value::UntrustedValue<#original_type>) -> Self {
+                #newtype(value)
+            }
+        }
+
+        #[automatically_derived]
+        impl ::core::convert::From<#newtype> for ::untrusted_value::UntrustedValue<#original_type> {
+            fn from(value: #newtype) -> Self {
+                value.0
+            }
+        }
+
+        #(#attrs)* #vis #sig {
+            #newtype(::untrusted_value::UntrustedValue::from(#block))
         }
     }
 }
+
+/// Returns true if the signature refers to the `Self` type
+fn refers_to_self(sig: &syn::Signature) -> bool {
+    fn contains_self(tokens: TokenStream) -> bool {
+        tokens.into_iter().any(|token| match token {
+            proc_macro2::TokenTree::Ident(ident) => ident == "Self",
+            proc_macro2::TokenTree::Group(group) => contains_self(group.stream()),
+            _ => false,
+        })
+    }
+    contains_self(quote! { #sig })
+}
+
+/// Returns true if the type contains a reference or a lifetime
+fn borrows(original_type: &Type) -> bool {
+    fn contains_borrow(tokens: TokenStream) -> bool {
+        tokens.into_iter().any(|token| match token {
+            proc_macro2::TokenTree::Punct(punct) => matches!(punct.as_char(), '&' | '\''),
+            proc_macro2::TokenTree::Group(group) => contains_borrow(group.stream()),
+            _ => false,
+        })
+    }
+    contains_borrow(quote! { #original_type })
+}
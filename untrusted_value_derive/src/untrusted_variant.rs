@@ -1,11 +1,12 @@
 use crate::extract_struct_fields_from_ast;
 use crate::sanitize_value::{
-    impl_sanitize_value_custom, FieldInfo, SanitizeValueMacroCustomParameters,
+    field_sanitizer, impl_sanitize_value_boxed, impl_sanitize_value_custom,
+    is_untrusted_value_type, struct_error, FieldInfo, SanitizeValueMacroCustomParameters,
 };
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::parse::Parse;
-use syn::{parse2, Data, Fields, Ident, Meta, Token};
+use syn::{parse2, parse_quote, Data, Field, Fields, Generics, Ident, Meta, Token, Type};
 
 #[derive(Default)]
 struct Parameters {
@@ -29,10 +30,114 @@ impl Parse for Parameters {
     }
 }
 
+#[derive(Default)]
+struct FieldParameters {
+    nested: bool,
+    nested_type: Option<Type>,
+}
+
+impl Parse for FieldParameters {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut parameters = FieldParameters::default();
+
+        while !input.is_empty() {
+            let ident: Ident = input.parse()?;
+            if ident == "nested" {
+                parameters.nested = true;
+                if input.parse::<Token![=]>().is_ok() {
+                    parameters.nested_type = Some(input.parse()?);
+                }
+            } else {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    "Unknown parameter, expected `nested`",
+                ));
+            }
+
+            // Consume an optional comma
+            let _ = input.parse::<Token![,]>().ok();
+        }
+
+        Ok(parameters)
+    }
+}
+
+impl FieldParameters {
+    fn from_field(field: &Field) -> Self {
+        field
+            .attrs
+            .iter()
+            .filter(|a| a.path().is_ident("untrusted"))
+            .map(|attribute| match attribute.meta {
+                Meta::List(ref meta) => parse2::<FieldParameters>(meta.tokens.clone())
+                    .expect("Expected a list of parameters within #[untrusted(...)]"),
+                _ => panic!("Expected a list of parameters within #[untrusted(...)]"),
+            })
+            .fold(FieldParameters::default(), |a, b| FieldParameters {
+                nested: a.nested || b.nested,
+                nested_type: b.nested_type.or(a.nested_type),
+            })
+    }
+}
+
 fn convert_struct_name_to_untrusted_variant(name: &Ident) -> Ident {
     Ident::new(&format!("{name}Untrusted"), name.span())
 }
 
+/// Type of the field inside the untrusted variant struct.
+/// Fields marked with `#[untrusted(nested)]` use the untrusted variant of their type,
+/// fields already typed `UntrustedValue<_>` are kept as is, all others are wrapped inside `UntrustedValue`.
+///
+/// The untrusted variant type is given by `#[untrusted(nested = Type)]`, otherwise it is
+/// derived from the name of the field type by appending `Untrusted` to the last path segment.
+fn untrusted_field_type(field: &Field, generics: &Generics) -> syn::Result<Type> {
+    let field_type = &field.ty;
+    let parameters = FieldParameters::from_field(field);
+
+    if !parameters.nested {
+        if is_untrusted_value_type(field_type) {
+            return Ok(field_type.clone());
+        }
+        return Ok(parse_quote!(::untrusted_value::UntrustedValue<#field_type>));
+    }
+    if let Some(nested_type) = parameters.nested_type {
+        return Ok(nested_type);
+    }
+
+    match field_type {
+        Type::Path(type_path) if type_path.qself.is_none() => {
+            if let Some(ident) = type_path.path.get_ident() {
+                if generics.type_params().any(|param| param.ident == *ident) {
+                    return Err(syn::Error::new_spanned(
+                        field_type,
+                        "The untrusted variant of a type parameter can not be derived from its name, \
+                         specify it using #[untrusted(nested = UntrustedType)]",
+                    ));
+                }
+            }
+
+            let mut nested_type = type_path.clone();
+            let segment = nested_type
+                .path
+                .segments
+                .last_mut()
+                .expect("Type path has no segments");
+            segment.ident = convert_struct_name_to_untrusted_variant(&segment.ident);
+            Ok(Type::Path(nested_type))
+        }
+        _ => Err(syn::Error::new_spanned(
+            field_type,
+            "#[untrusted(nested)] requires a struct type, \
+             specify the untrusted variant using #[untrusted(nested = UntrustedType)]",
+        )),
+    }
+}
+
+/// Fields already typed `UntrustedValue<_>` are not wrapped again and stay tainted in the sanitized struct.
+fn is_passed_through(field: &Field) -> bool {
+    !FieldParameters::from_field(field).nested && is_untrusted_value_type(&field.ty)
+}
+
 #[allow(clippy::too_many_lines)] // need to refactor this in the future
 fn impl_untrusted_variant_of_struct(
     parameters: &Parameters,
@@ -42,39 +147,62 @@ fn impl_untrusted_variant_of_struct(
     let struct_visibility = &ast.vis;
     let new_struct_name = convert_struct_name_to_untrusted_variant(name);
 
-    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    let generics = &ast.generics;
+    let (_, ty_generics, where_clause) = generics.split_for_impl();
 
-    let modified_fields = extract_struct_fields_from_ast(ast).iter().map(|f| {
-        let field_name = &f.ident;
-        let field_type = &f.ty;
-        let visibility = &f.vis;
-        quote! {
-            #visibility #field_name: ::untrusted_value::UntrustedValue<#field_type>,
-        }
-    });
+    let field_types = extract_struct_fields_from_ast(ast)
+        .iter()
+        .map(|f| untrusted_field_type(f, generics))
+        .collect::<syn::Result<Vec<_>>>();
+    let field_types = match field_types {
+        Ok(field_types) => field_types,
+        Err(error) => return error.to_compile_error(),
+    };
+
+    let modified_fields = extract_struct_fields_from_ast(ast)
+        .iter()
+        .zip(&field_types)
+        .map(|(f, field_type)| {
+            let field_name = &f.ident;
+            let visibility = &f.vis;
+            quote! {
+                #visibility #field_name: #field_type,
+            }
+        });
 
     let fields: Vec<FieldInfo> = extract_struct_fields_from_ast(ast)
         .iter()
-        .map(|f| {
+        .zip(field_types.iter().cloned())
+        .map(|(f, new_type)| {
             let field_name = &f.ident;
             let field_type = &f.ty;
-            let new_type = syn::parse_quote!(untrusted_value::UntrustedValue<#field_type>);
+            let sanitizer = field_sanitizer(f);
             FieldInfo {
                 name: field_name,
                 field_type: new_type,
                 field_target_type: field_type.clone(),
+                sanitize: sanitizer.is_some() || !is_passed_through(f),
+                sanitizer,
             }
         })
         .collect();
 
+    let error = struct_error(ast);
+    assert!(
+        error.is_some() || fields.iter().all(|f| f.sanitizer.is_none()),
+        "Fields with #[sanitize_with(...)] require #[sanitize_value(error = ErrorType)] on the struct"
+    );
+
     let new_struct_type = syn::parse_quote!(#new_struct_name #ty_generics);
     let struct_type = syn::parse_quote!(#name #ty_generics);
     let params = SanitizeValueMacroCustomParameters {
         struct_type: &new_struct_type,
         struct_type_target: &struct_type,
         fields,
-        impl_generics,
-        where_clause,
+        unwrap_source: false,
+        error,
+        with_report: false,
+        generics,
     };
 
     let sanitize_value_derive = parameters
@@ -83,17 +211,14 @@ fn impl_untrusted_variant_of_struct(
         .any(|d| d == "SanitizeValue");
     let sanitize_value_derive = if sanitize_value_derive {
         let derive = impl_sanitize_value_custom(params);
+        let boxed = impl_sanitize_value_boxed(name, &ast.generics);
 
-        let where_clause_with_error_bound = {
-            let prefix = if where_clause.is_none() {
-                quote! { where }
-            } else {
-                quote! { #where_clause, }
-            };
-            quote! {
-                #prefix #new_struct_name #ty_generics: ::untrusted_value::SanitizeValue<#name #ty_generics, Error = CommonSanitizationError>
-            }
-        };
+        let mut generics = ast.generics.clone();
+        generics.params.push(parse_quote!(CommonSanitizationError));
+        generics.make_where_clause().predicates.push(parse_quote!(
+            #new_struct_name #ty_generics: ::untrusted_value::SanitizeValue<#name #ty_generics, Error = CommonSanitizationError>
+        ));
+        let (impl_generics, _, where_clause) = generics.split_for_impl();
 
         quote! {
             // UNTRUSTED STRUCT -> sanitize_value -> STRUCT
@@ -102,12 +227,16 @@ fn impl_untrusted_variant_of_struct(
             // UntrustedValue<STRUCT> -> sanitize_value -> STRUCT
             //  by STRUCT -> into_untrusted_variant -> UNTRUSTED STRUCT -> sanitize_value -> STRUCT
             #[automatically_derived]
-            impl<CommonSanitizationError> ::untrusted_value::SanitizeValue<#name #ty_generics> for ::untrusted_value::UntrustedValue<#name #ty_generics> #where_clause_with_error_bound {
+            impl #impl_generics ::untrusted_value::SanitizeValue<#name #ty_generics> for ::untrusted_value::UntrustedValue<#name #ty_generics> #where_clause {
                 type Error = CommonSanitizationError;
                 fn sanitize_value(self) -> Result<#name #ty_generics, Self::Error> {
-                    self.use_untrusted_value().to_untrusted_variant().sanitize_value()
+                    let untrusted_variant: #new_struct_name #ty_generics = ::untrusted_value::IntoUntrustedVariant::to_untrusted_variant(self.use_untrusted_value());
+                    ::untrusted_value::SanitizeValue::sanitize_value(untrusted_variant)
                 }
             }
+
+            // UntrustedValue<Box<STRUCT>> -> sanitize_value -> Box<STRUCT>
+            #boxed
         }
     } else {
         quote! {}
@@ -118,18 +247,30 @@ fn impl_untrusted_variant_of_struct(
         .iter()
         .any(|d| d == "SanitizeValueEnd");
     let sanitize_value_end_derive = if sanitize_value_end_derive {
+        let boxed = impl_sanitize_value_boxed(name, &ast.generics);
+        let mut generics = ast.generics.clone();
+        generics.params.push(parse_quote!(CommonSanitizationError));
+        generics
+            .make_where_clause()
+            .predicates
+            .extend::<[syn::WherePredicate; 2]>([
+                parse_quote!(#name #ty_generics: ::untrusted_value::IntoUntrustedVariant<#new_struct_name #ty_generics>),
+                parse_quote!(#new_struct_name #ty_generics: ::untrusted_value::SanitizeValue<#name #ty_generics, Error = CommonSanitizationError>),
+            ]);
+        let (impl_generics, _, where_clause) = generics.split_for_impl();
+
         quote! {
             #[automatically_derived]
-            impl<CommonSanitizationError> ::untrusted_value::SanitizeValue<#name> for ::untrusted_value::UntrustedValue<#name>
-            where
-                #name: ::untrusted_value::IntoUntrustedVariant<#new_struct_name>,
-                #new_struct_name: ::untrusted_value::SanitizeValue<#name, Error=CommonSanitizationError>
-            {
+            impl #impl_generics ::untrusted_value::SanitizeValue<#name #ty_generics> for ::untrusted_value::UntrustedValue<#name #ty_generics> #where_clause {
                 type Error = CommonSanitizationError;
-                fn sanitize_value(self) -> std::result::Result<#name, Self::Error> {
-                    self.use_untrusted_value().to_untrusted_variant().sanitize_value()
+                fn sanitize_value(self) -> Result<#name #ty_generics, Self::Error> {
+                    let untrusted_variant: #new_struct_name #ty_generics = ::untrusted_value::IntoUntrustedVariant::to_untrusted_variant(self.use_untrusted_value());
+                    ::untrusted_value::SanitizeValue::sanitize_value(untrusted_variant)
                 }
             }
+
+            // UntrustedValue<Box<STRUCT>> -> sanitize_value -> Box<STRUCT>
+            #boxed
         }
     } else {
         quote! {}
@@ -140,8 +281,38 @@ fn impl_untrusted_variant_of_struct(
         "SanitizeValueEnd derive can not be used together with SanitizeValue derive"
     );
 
+    let redacted_debug_derive = parameters
+        .derive_macros
+        .iter()
+        .any(|d| d == "RedactedDebug");
+    let redacted_debug_derive = if redacted_debug_derive {
+        impl_redacted_debug(ast, &new_struct_name)
+    } else {
+        quote! {}
+    };
+
+    let redacted_display_derive = parameters
+        .derive_macros
+        .iter()
+        .any(|d| d == "RedactedDisplay");
+    let redacted_display_derive = if redacted_display_derive {
+        impl_redacted_display(ast, &new_struct_name)
+    } else {
+        quote! {}
+    };
+
+    let derive_errors = parameters.derive_macros.iter().filter_map(|d| {
+        unsupported_derive_message(d)
+            .map(|message| syn::Error::new(d.span(), message).to_compile_error())
+    });
+
     let derive_macros = parameters.derive_macros.iter().map(|d| {
-        if d == "SanitizeValue" || d == "SanitizeValueEnd" {
+        if d == "SanitizeValue"
+            || d == "SanitizeValueEnd"
+            || d == "RedactedDebug"
+            || d == "RedactedDisplay"
+            || unsupported_derive_message(d).is_some()
+        {
             quote! {}
         } else {
             quote! {
@@ -151,9 +322,11 @@ fn impl_untrusted_variant_of_struct(
     });
 
     quote! {
+        #(#derive_errors)*
+
         #[automatically_derived]
         #(#derive_macros)*
-        #struct_visibility struct #new_struct_name #ty_generics #where_clause {
+        #struct_visibility struct #new_struct_name #generics #where_clause {
             #(#modified_fields)*
         }
 
@@ -163,80 +336,199 @@ fn impl_untrusted_variant_of_struct(
 
         // UntrustedValue<STRUCT> -> sanitize_value -> STRUCT
         #sanitize_value_end_derive
+
+        // UNTRUSTED STRUCT: Debug, without printing the field values
+        #redacted_debug_derive
+
+        // UNTRUSTED STRUCT: Display, without printing the field values
+        #redacted_display_derive
+    }
+}
+
+/// Returns an error message if the given trait can not be derived for the untrusted variant.
+fn unsupported_derive_message(derive_macro: &Ident) -> Option<String> {
+    if derive_macro == "Debug" {
+        Some(
+            "The untrusted variant can not derive `Debug`, since `UntrustedValue` does not \
+             implement `Debug` to not leak tainted values. \
+             Use `#[untrusted_derive(RedactedDebug)]` instead."
+                .to_string(),
+        )
+    } else if derive_macro == "Hash" || derive_macro == "PartialEq" || derive_macro == "Eq" {
+        Some(format!(
+            "The untrusted variant can not derive `{derive_macro}`, since `UntrustedValue` does \
+             not implement `{derive_macro}`. Comparing or hashing tainted values would allow \
+             using them without sanitization."
+        ))
+    } else {
+        None
+    }
+}
+
+fn field_accessor(index: usize, field: &Field) -> TokenStream {
+    if let Some(ident) = &field.ident {
+        quote! { #ident }
+    } else {
+        let index = syn::Index::from(index);
+        quote! { #index }
+    }
+}
+
+fn impl_redacted_debug(ast: &syn::DeriveInput, new_struct_name: &Ident) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    let struct_name = new_struct_name.to_string();
+
+    let debug_fields = extract_struct_fields_from_ast(ast).iter().map(|f| {
+        if let Some(field_name) = &f.ident {
+            let field_name = field_name.to_string();
+            quote! {
+                .field(#field_name, &::core::format_args!("<redacted>"))
+            }
+        } else {
+            quote! {
+                .field(&::core::format_args!("<redacted>"))
+            }
+        }
+    });
+
+    let debug_builder = match &ast.data {
+        Data::Struct(data_struct) if matches!(data_struct.fields, Fields::Unnamed(_)) => {
+            quote! { debug_tuple }
+        }
+        _ => quote! { debug_struct },
+    };
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics ::core::fmt::Debug for #new_struct_name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.#debug_builder(#struct_name)
+                    #(#debug_fields)*
+                    .finish()
+            }
+        }
+    }
+}
+
+fn impl_redacted_display(ast: &syn::DeriveInput, new_struct_name: &Ident) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+    let redacted = extract_struct_fields_from_ast(ast)
+        .iter()
+        .enumerate()
+        .map(|(index, f)| field_accessor(index, f).to_string() + ": ***")
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics ::core::fmt::Display for #new_struct_name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.write_str(#redacted)
+            }
+        }
     }
 }
 
 pub fn impl_untrusted_variant_macro(ast: &syn::DeriveInput) -> TokenStream {
     let name = &ast.ident;
+
+    if let Err(error) = extract_struct_fields_from_ast(ast)
+        .iter()
+        .try_for_each(|f| untrusted_field_type(f, &ast.generics).map(|_| ()))
+    {
+        return error.to_compile_error();
+    }
     let new_struct_name = convert_struct_name_to_untrusted_variant(name);
 
     let parameter = ast
         .attrs
         .iter()
-        .find(|a| a.path().segments.len() == 1 && a.path().segments[0].ident == "untrusted_derive")
-        .map_or_else(Parameters::default, |attribute| match attribute.meta {
+        .filter(|a| {
+            a.path().segments.len() == 1 && a.path().segments[0].ident == "untrusted_derive"
+        })
+        .map(|attribute| match attribute.meta {
             Meta::List(ref meta) => parse2::<Parameters>(meta.tokens.clone())
                 .expect("Expected a list of traits to derive within #[untrusted_derive(...)]"),
             _ => Parameters::default(),
+        })
+        .fold(Parameters::default(), |mut a, b| {
+            a.derive_macros.extend(b.derive_macros);
+            a
         });
 
-    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
-
-    let fields_wrap_into_untrusted = match &ast.data {
-        Data::Struct(data_struct) => match &data_struct.fields {
-            Fields::Named(fields_named) => {
-                let field_names = fields_named.named.iter().map(|f| &f.ident);
-                quote! {
-                    #(
-                        #field_names: ::untrusted_value::UntrustedValue::from(self.#field_names),
-                    )*
-                }
-            }
-            Fields::Unnamed(fields_unnamed) => {
-                let indices = 0..fields_unnamed.unnamed.len();
-                quote! {
-                    #(
-                        ::untrusted_value::UntrustedValue::from(self.#indices),
-                    )*
-                }
-            }
-            Fields::Unit => quote! {},
-        },
-        _ => panic!("Only structs are supported"),
-    };
-
-    let fields_wrap_from_untrusted = match &ast.data {
-        Data::Struct(data_struct) => match &data_struct.fields {
-            Fields::Named(fields_named) => {
-                let field_names = fields_named.named.iter().map(|f| &f.ident);
-                quote! {
-                    #(
-                        #field_names: self.#field_names.use_untrusted_value(),
-                    )*
-                }
-            }
-            Fields::Unnamed(fields_unnamed) => {
-                let indices = 0..fields_unnamed.unnamed.len();
-                quote! {
-                    #(
-                        self.#indices.use_untrusted_value(),
-                    )*
-                }
-            }
-            Fields::Unit => quote! {},
-        },
-        _ => panic!("Only structs are supported"),
-    };
+    let fields_wrap_into_untrusted =
+        extract_struct_fields_from_ast(ast)
+            .iter()
+            .enumerate()
+            .map(|(index, f)| {
+                let accessor = field_accessor(index, f);
+                let value = if FieldParameters::from_field(f).nested {
+                    quote! { self.#accessor.to_untrusted_variant() }
+                } else if is_passed_through(f) {
+                    quote! { self.#accessor }
+                } else {
+                    quote! { ::untrusted_value::UntrustedValue::from(self.#accessor) }
+                };
+                quote! { #accessor: #value, }
+            });
+
+    let fields_wrap_from_untrusted =
+        extract_struct_fields_from_ast(ast)
+            .iter()
+            .enumerate()
+            .map(|(index, f)| {
+                let accessor = field_accessor(index, f);
+                let value = if FieldParameters::from_field(f).nested {
+                    quote! { self.#accessor.to_untrusted_variant().use_untrusted_value() }
+                } else if is_passed_through(f) {
+                    quote! { self.#accessor }
+                } else {
+                    quote! { self.#accessor.use_untrusted_value() }
+                };
+                quote! { #accessor: #value, }
+            });
 
     let untrusted_struct = impl_untrusted_variant_of_struct(&parameter, ast);
 
-    let sanitize_with = super::sanitize_with::impl_sanitize_with_custom(
-        &new_struct_name,
-        &ast.generics,
+    let sanitize_with =
+        super::sanitize_with::impl_sanitize_with_custom(&new_struct_name, name, &ast.generics);
+
+    let into_untrusted_variant = impl_into_untrusted_variant(
         name,
+        &new_struct_name,
         &ast.generics,
+        &quote! { #(#fields_wrap_into_untrusted)* },
+        &quote! { #(#fields_wrap_from_untrusted)* },
     );
 
+    quote! {
+        // STRUCT <-> UNTRUSTED STRUCT <-> UntrustedValue<STRUCT>
+        #into_untrusted_variant
+
+        // UNTRUSTED STRUCT -> sanitize_with -> STRUCT
+        #sanitize_with
+
+        // UNTRUSTED STRUCT
+        // SanitizeValueDerive: UNTRUSTED STRUCT -> sanitize_value -> STRUCT
+        // SanitizeValueDerive: UntrustedValue<STRUCT> -> sanitize_value -> STRUCT
+        #untrusted_struct
+    }
+}
+
+/// Implements the conversions between the struct, its untrusted variant and `UntrustedValue<STRUCT>`.
+///
+/// `fields_wrap_into_untrusted` initializes the fields of the untrusted variant from `self: STRUCT`,
+/// `fields_wrap_from_untrusted` initializes the fields of the struct from `self: UNTRUSTED STRUCT`.
+pub fn impl_into_untrusted_variant(
+    name: &Ident,
+    new_struct_name: &Ident,
+    generics: &Generics,
+    fields_wrap_into_untrusted: &TokenStream,
+    fields_wrap_from_untrusted: &TokenStream,
+) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
     quote! {
         // STRUCT -> into_untrusted_variant -> UNTRUSTED STRUCT
         #[automatically_derived]
@@ -264,7 +556,7 @@ pub fn impl_untrusted_variant_macro(ast: &syn::DeriveInput) -> TokenStream {
         #[automatically_derived]
         impl #impl_generics ::untrusted_value::IntoUntrustedVariant<#new_struct_name #ty_generics> for ::untrusted_value::UntrustedValue<#name #ty_generics> #where_clause {
             fn to_untrusted_variant(self) -> #new_struct_name #ty_generics {
-                self.use_untrusted_value().to_untrusted_variant()
+                ::untrusted_value::IntoUntrustedVariant::to_untrusted_variant(self.use_untrusted_value())
             }
         }
 
@@ -272,16 +564,8 @@ pub fn impl_untrusted_variant_macro(ast: &syn::DeriveInput) -> TokenStream {
         #[automatically_derived]
         impl #impl_generics From<#name #ty_generics> for #new_struct_name #ty_generics #where_clause {
             fn from(value: #name #ty_generics) -> Self {
-                value.to_untrusted_variant()
+                ::untrusted_value::IntoUntrustedVariant::to_untrusted_variant(value)
             }
         }
-
-        // UNTRUSTED STRUCT -> sanitize_with -> STRUCT
-        #sanitize_with
-
-        // UNTRUSTED STRUCT
-        // SanitizeValueDerive: UNTRUSTED STRUCT -> sanitize_value -> STRUCT
-        // SanitizeValueDerive: UntrustedValue<STRUCT> -> sanitize_value -> STRUCT
-        #untrusted_struct
     }
 }
@@ -0,0 +1,133 @@
+use crate::extract_struct_fields_from_ast;
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::parse::Parse;
+use syn::{parse2, Field, Ident, Index, Member, Meta, Path, Token, Type};
+
+/// Parameters of the `#[validate_with(error = ErrorType)]` attribute on the struct
+struct Parameters {
+    error: Type,
+}
+
+impl Parse for Parameters {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let ident: Ident = input.parse()?;
+        if ident != "error" {
+            return Err(syn::Error::new(
+                ident.span(),
+                "Unknown parameter, expected `error`",
+            ));
+        }
+        input.parse::<Token![=]>()?;
+        let error: Type = input.parse()?;
+
+        // Consume an optional comma
+        let _ = input.parse::<Token![,]>().ok();
+
+        Ok(Parameters { error })
+    }
+}
+
+/// Returns the tokens of the `#[validate_with(...)]` attribute, if present
+fn validate_attribute(attrs: &[syn::Attribute]) -> Option<TokenStream> {
+    attrs
+        .iter()
+        .find(|a| a.path().is_ident("validate_with"))
+        .map(|attribute| match attribute.meta {
+            Meta::List(ref meta) => meta.tokens.clone(),
+            _ => panic!("Expected a list of parameters within #[validate_with(...)]"),
+        })
+}
+
+/// Returns the validator function path of the field, given by `#[validate_with(validator)]`
+fn field_validator(field: &Field) -> syn::Result<Path> {
+    let tokens = validate_attribute(&field.attrs).ok_or_else(|| {
+        syn::Error::new_spanned(
+            field,
+            "Each field requires a validator, add #[validate_with(validator)] to the field",
+        )
+    })?;
+    parse2::<Path>(tokens)
+}
+
+fn field_member(index: usize, field: &Field) -> Member {
+    match &field.ident {
+        Some(ident) => Member::Named(ident.clone()),
+        None => Member::Unnamed(Index::from(index)),
+    }
+}
+
+pub fn impl_validate_in_place_macro(ast: &syn::DeriveInput) -> TokenStream {
+    let name = &ast.ident;
+
+    let Parameters { error } = parse2::<Parameters>(
+        validate_attribute(&ast.attrs)
+            .expect("Expected #[validate_with(error = ErrorType)] on the struct"),
+    )
+    .expect("Expected #[validate_with(error = ErrorType)] on the struct");
+
+    let validations = extract_struct_fields_from_ast(ast)
+        .iter()
+        .enumerate()
+        .map(|(index, field)| {
+            let member = field_member(index, field);
+            let validator = field_validator(field)?;
+            let result = Ident::new(&format!("validation_{index}"), Span::call_site());
+            Ok((
+                result,
+                quote! {
+                    #validator(&value.#member).map_err(::core::convert::Into::<#error>::into)
+                },
+            ))
+        })
+        .collect::<syn::Result<Vec<_>>>();
+    let validations = match validations {
+        Ok(validations) => validations,
+        Err(error) => return error.to_compile_error(),
+    };
+
+    let validate_fields = {
+        #[cfg(not(feature = "harden_sanitize"))]
+        {
+            let checks = validations.iter().map(|(_, validation)| {
+                quote! {
+                    #validation?;
+                }
+            });
+            quote! {
+                #(#checks)*
+            }
+        }
+        #[cfg(feature = "harden_sanitize")]
+        {
+            let checks = validations.iter().map(|(result, validation)| {
+                quote! {
+                    let #result = #validation;
+                }
+            });
+            let errors = validations.iter().map(|(result, _)| {
+                quote! {
+                    #result?;
+                }
+            });
+            quote! {
+                #(#checks)*
+                #(#errors)*
+            }
+        }
+    };
+
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics ::untrusted_value::SanitizeValue<#name #ty_generics> for ::untrusted_value::UntrustedValue<#name #ty_generics> #where_clause {
+            type Error = #error;
+            fn sanitize_value(self) -> Result<#name #ty_generics, Self::Error> {
+                let value = ::untrusted_value::UntrustedValue::use_untrusted_value(self);
+                #validate_fields
+                Ok(value)
+            }
+        }
+    }
+}